@@ -3,6 +3,9 @@ use crate::tok::{Delimiters, Keywords, Operators, StringKind, TokenType};
 use std::collections::HashMap;
 use std::iter::Peekable;
 use std::str::Chars;
+use unicode_ident::{is_xid_continue, is_xid_start};
+use unicode_normalization::UnicodeNormalization;
+use unicode_security::{skeleton, MixedScript};
 //use crate::lexer::error::{LexerError, LexerErrorType, Position};
 
 //#[allow(dead_code)]
@@ -50,7 +53,22 @@ pub struct Lexer<'a> {
     syntax_mode: SyntaxMode,
     indent_level: Vec<usize>,
     at_line_start: bool,
-    // nesting :usize
+    // squelettes (UTS #39) des identifiants deja vus, pour detecter les confusables
+    identifier_skeletons: HashMap<String, String>,
+    warnings: Vec<LexerError>,
+    // vrai une fois le token EOF emis par l'iterateur
+    finished: bool,
+    // profondeur des (), [] et {} ouverts : pas de NEWLINE/INDENT/DEDENT a l'interieur
+    nesting: usize,
+    // largeur d'une tabulation pour le calcul des colonnes d'indentation
+    tab_width: usize,
+    // caractere d'indentation (' ' ou '\t') fixe par la premiere ligne indentee du fichier
+    indent_char: Option<char>,
+    // DEDENT restant a emettre quand une ligne ferme plusieurs blocs
+    pending_dedents: usize,
+    // position en octets dans le source, et debut du dernier token (pour le CST)
+    offset: usize,
+    token_start: usize,
 }
 
 /// Implementation du lexer avec tous les methodes pour classer les tokens
@@ -70,25 +88,71 @@ impl<'a> Lexer<'a> {
             syntax_mode,
             indent_level: vec![0],
             at_line_start: true,
-            //  nesting: 0,
+            identifier_skeletons: HashMap::new(),
+            warnings: Vec::new(),
+            finished: false,
+            nesting: 0,
+            tab_width: 4,
+            indent_char: None,
+            pending_dedents: 0,
+            offset: 0,
+            token_start: 0,
         };
         lexer
     }
 
+    /// Configure la largeur d'une tabulation (4 par defaut)
+    pub fn with_tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width;
+        self
+    }
+
     /// La Logique de la Methode pour compter l'indentation a ete trasferer dans la methode get_token()
     /// Methode pour compter l'indentation
 
     pub fn count_indentation(&mut self) -> usize {
+        self.read_indentation().0
+    }
+
+    /// Lit l'indentation en debut de ligne et renvoie (colonne, espaces utilises, tabulations utilisees)
+    fn read_indentation(&mut self) -> (usize, bool, bool) {
         let mut count = 0;
+        let mut uses_spaces = false;
+        let mut uses_tabs = false;
         while let Some(&ch) = self.source.peek() {
             match ch {
-                ' ' => count += 1,
-                '\t' => count += 4, // ou une autre valeur selon votre convention
+                ' ' => {
+                    count += 1;
+                    uses_spaces = true;
+                }
+                '\t' => {
+                    count += self.tab_width;
+                    uses_tabs = true;
+                }
                 _ => break,
             }
             self.advance();
         }
-        count
+        (count, uses_spaces, uses_tabs)
+    }
+
+    /// Verifie que le fichier n'utilise qu'un seul type de caractere pour l'indentation
+    fn check_indentation_style(&mut self, uses_spaces: bool, uses_tabs: bool) -> Option<TokenType> {
+        let indent_char = match (uses_spaces, uses_tabs) {
+            (true, true) => return Some(TokenType::ERROR(LexerError::mixed_tabs_and_spaces(self.current_position()))),
+            (true, false) => ' ',
+            (false, true) => '\t',
+            (false, false) => return None,
+        };
+        match self.indent_char {
+            Some(expected) if expected != indent_char => {
+                Some(TokenType::ERROR(LexerError::mixed_tabs_and_spaces(self.current_position())))
+            }
+            _ => {
+                self.indent_char = Some(indent_char);
+                None
+            }
+        }
     }
 
     /// Creation d'une hashmap pour les mots cles
@@ -124,6 +188,7 @@ impl<'a> Lexer<'a> {
         keywords.insert("loop".to_string(), Keywords::LOOP);
         keywords.insert("match".to_string(), Keywords::MATCH);
         keywords.insert("mod".to_string(), Keywords::MOD);
+        keywords.insert("move".to_string(), Keywords::MOVE);
         keywords.insert("mut".to_string(), Keywords::MUT);
         keywords.insert("none".to_string(), Keywords::NONE);
         keywords.insert("not".to_string(), Keywords::NOT);
@@ -173,7 +238,7 @@ impl<'a> Lexer<'a> {
         operators.insert("++".to_string(), Operators::PLUSEQUAL);
         operators.insert("--".to_string(), Operators::MINEQUAL);
         operators.insert("**".to_string(), Operators::DOUBLESTAR);
-        //operators.insert("//".to_string(), Operators::DOUBLESLASH);
+        operators.insert("//".to_string(), Operators::DOUBLESLASH);
         operators.insert("&&".to_string(), Operators::AND);
         operators.insert("||".to_string(), Operators::OR);
         operators.insert("!".to_string(), Operators::EXCLAMATION);
@@ -236,6 +301,7 @@ impl<'a> Lexer<'a> {
     fn next_char(&mut self) -> Option<char> {
         let ch = self.source.next()?;
         self.current_char = ch;
+        self.offset += ch.len_utf8();
         if ch == '\n' {
             self.current_line += 1;
             self.current_column = 1;
@@ -273,31 +339,53 @@ impl<'a> Lexer<'a> {
     /// methode pour obtenir le token
 
     pub fn get_token(&mut self) -> Option<TokenType> {
+        self.token_start = self.offset;
+
+        // DEDENT restants d'une ligne qui ferme plusieurs blocs
+        if self.pending_dedents > 0 {
+            self.pending_dedents -= 1;
+            return Some(TokenType::DEDENT);
+        }
+
         //Gérer l'indentation au début d'une nouvelle ligne
-        if self.at_line_start && self.syntax_mode == SyntaxMode::Indentation {
+        if self.at_line_start && self.syntax_mode == SyntaxMode::Indentation && self.nesting == 0 {
             self.at_line_start = false;
-            let current_indent = self.count_indentation();
-            let previous_indent = *self.indent_level.last().unwrap_or(&0);
-
-            if current_indent > previous_indent {
-                self.indent_level.push(current_indent);
-                return Some(TokenType::INDENT);
-            } else if current_indent < previous_indent {
-                while current_indent < *self.indent_level.last().unwrap_or(&0) {
-                    self.indent_level.pop();
-                    return Some(TokenType::DEDENT);
+            let (current_indent, uses_spaces, uses_tabs) = self.read_indentation();
+
+            // les lignes vides ou de commentaire n'ont pas d'effet sur l'indentation
+            if !matches!(self.peek_char(), Some('\n') | Some('\r') | Some('#')) {
+                if let Some(error) = self.check_indentation_style(uses_spaces, uses_tabs) {
+                    return Some(error);
                 }
-                if current_indent != *self.indent_level.last().unwrap_or(&0) {
-                    return Some(TokenType::ERROR(LexerError::invalid_indentation(
-                        Position {
-                            line: self.current_line,
-                            column: self.current_column,
-                        },
-                    )));
+
+                let previous_indent = *self.indent_level.last().unwrap_or(&0);
+                if current_indent > previous_indent {
+                    self.indent_level.push(current_indent);
+                    return Some(TokenType::INDENT);
+                } else if current_indent < previous_indent {
+                    let expected = self.indent_level.clone();
+                    let mut dedents = 0;
+                    while current_indent < *self.indent_level.last().unwrap_or(&0) {
+                        self.indent_level.pop();
+                        dedents += 1;
+                    }
+                    // le dedent doit retomber exactement sur un niveau deja ouvert ; les
+                    // blocs deja fermes recoivent quand meme leur DEDENT apres l'erreur
+                    if current_indent != *self.indent_level.last().unwrap_or(&0) {
+                        self.pending_dedents = dedents;
+                        return Some(TokenType::ERROR(LexerError::indentation_error(
+                            current_indent,
+                            expected,
+                            self.current_position(),
+                        )));
+                    }
+                    self.pending_dedents = dedents - 1;
+                    return Some(TokenType::DEDENT);
                 }
             }
             // Si l'indentation est la même, on ne fait rien de spécial
         }
+        self.at_line_start = false;
 
         // if self.at_line_start && self.syntax_mode == SyntaxMode::Indentation {
         //     self.at_line_start = false;
@@ -321,6 +409,7 @@ impl<'a> Lexer<'a> {
         // }
 
         self.skip_whitespace(); // Sauter les espaces et tabulations
+        self.token_start = self.offset;
 
         // Vérifier le prochain caractère
         match self.peek_char() {
@@ -342,12 +431,14 @@ impl<'a> Lexer<'a> {
             }
 
             Some('0'..='9') => Some(self.lex_number()),
-            Some('a'..='z') | Some('A'..='Z') | Some('_') => Some(self.lex_identifier_or_keyword()),
+            Some(ch) if ch == '_' || is_xid_start(ch) => Some(self.lex_identifier_or_keyword()),
             Some('"') | Some('\'') => Some(self.lex_string()),
             Some('#') => Some(self.lex_comment()),
             Some('/') => {
                 if let Some(next_char) = self.peek_next_char() {
                     match next_char {
+                        // En mode indentation les commentaires sont '#' : '//' est la division entiere
+                        '/' if self.syntax_mode == SyntaxMode::Indentation => self.lex_operator(),
                         '/' | '*' => Some(self.lex_comment()),
                         _ => self.lex_operator(),
                     }
@@ -355,7 +446,11 @@ impl<'a> Lexer<'a> {
                     self.lex_operator()
                 }
             }
-            Some(ch) if self.delimiters.contains_key(&ch.to_string()) => Some(self.lex_delimiter()),
+            Some(ch) if self.delimiters.contains_key(&ch.to_string()) => {
+                let token = self.lex_delimiter();
+                self.update_nesting(&token);
+                Some(token)
+            }
             Some(ch) if !ch.is_alphanumeric() => self.lex_operator(),
             None => {
                 ////////////à surveiller si c'est correct et pas redondant
@@ -487,10 +582,11 @@ impl<'a> Lexer<'a> {
 
     //fn lex_identifier(){}
     /// Methode pour les different types de token de Type Identifier ou Keyword
+    /// Les identifiants suivent UAX #31 (XID_Start / XID_Continue) et sont normalises en NFC
     fn lex_identifier_or_keyword(&mut self) -> TokenType {
         self.current_token_text.clear();
         while let Some(&ch) = self.source.peek() {
-            if ch.is_alphanumeric() || ch == '_' {
+            if ch == '_' || is_xid_continue(ch) {
                 let ch = self.advance();
                 self.current_token_text.push(ch); // Ajoute le caractère à la chaîne de texte du token
             } else {
@@ -498,15 +594,58 @@ impl<'a> Lexer<'a> {
             }
         }
 
+        if !self.current_token_text.is_ascii() {
+            self.current_token_text = self.current_token_text.nfc().collect();
+
+            // un identifiant melangeant plusieurs ecritures (latin + cyrillique, ...) est refuse
+            if !self.current_token_text.as_str().is_single_script() {
+                return TokenType::ERROR(LexerError::mixed_script_identifier(&self.current_token_text, self.current_position()));
+            }
+        }
+
         if let Some(keyword) = self.keywords.get(&self.current_token_text) {
             TokenType::KEYWORD(keyword.clone()) // c'est un mot clé
         } else {
+            self.check_confusable_identifier();
             TokenType::IDENTIFIER {
                 name: self.current_token_text.clone(),
             } // sinon c'est un identifiant
         }
     }
 
+    /// Enregistre un avertissement si l'identifiant courant est visuellement
+    /// confondable avec un identifiant deja rencontre (UTS #39)
+    fn check_confusable_identifier(&mut self) {
+        let name = self.current_token_text.clone();
+        let key: String = skeleton(&name).collect();
+        match self.identifier_skeletons.get(&key) {
+            Some(other) if *other != name => {
+                if !name.is_ascii() || !other.is_ascii() {
+                    let position = Position {
+                        line: self.current_line,
+                        column: self.current_column,
+                    };
+                    let warning = LexerError::confusable_identifier(&name, other, position);
+                    self.warnings.push(warning);
+                }
+            }
+            Some(_) => {}
+            None => {
+                self.identifier_skeletons.insert(key, name);
+            }
+        }
+    }
+
+    /// Intervalle (en octets) du source couvert par le dernier token produit
+    pub(crate) fn token_span(&self) -> std::ops::Range<usize> {
+        self.token_start..self.offset
+    }
+
+    /// Avertissements non bloquants emis pendant l'analyse (identifiants confusables, ...)
+    pub fn warnings(&self) -> &[LexerError] {
+        &self.warnings
+    }
+
     fn lex_string(&mut self) -> TokenType {
         self.current_token_text.clear();
 
@@ -583,7 +722,16 @@ impl<'a> Lexer<'a> {
             if self.operators.contains_key(&op) {
                 self.advance();
                 self.current_token_text.push(next_char);
-                return Some(TokenType::OPERATOR(self.operators[&op].clone()));
+
+                // Operateurs de trois caracteres : '**=', '//=', '<<=', '>>=', '..='
+                if let Some(third_char) = self.peek_char() {
+                    op.push(third_char);
+                    if self.operators.contains_key(&op) {
+                        self.advance();
+                        self.current_token_text.push(third_char);
+                    }
+                }
+                return Some(TokenType::OPERATOR(self.operators[&self.current_token_text].clone()));
             }
         }
 
@@ -622,6 +770,13 @@ impl<'a> Lexer<'a> {
                 return TokenType::DELIMITER(Delimiters::DOUBLECOLON);
             }
 
+            // verifie pour ":=" (expression d'assignation)
+            if combined == ":=" {
+                self.advance();
+                self.current_token_text = combined;
+                return TokenType::OPERATOR(Operators::COLONEQUAL);
+            }
+
             if first_char == '.' {
                 if let Some(&next_char) = self.source.peek(){
                     if next_char == '.'{
@@ -743,6 +898,7 @@ impl<'a> Lexer<'a> {
     /// Methode pour avancer d'un character
     fn advance(&mut self) -> char {
         let ch = self.source.next().unwrap();
+        self.offset += ch.len_utf8();
         if ch == '\n' {
             self.current_line += 1; // Incrémenter le numéro de ligne
             self.current_column = 1; // Réinitialiser le numéro de colonne
@@ -761,11 +917,21 @@ impl<'a> Lexer<'a> {
                     if self.syntax_mode == SyntaxMode::Braces{
                         self.advance();
                         self.at_line_start = true;
+                    }else if self.nesting > 0 {
+                        // jonction implicite des lignes a l'interieur des delimiteurs ouverts
+                        self.advance();
                     }else {
                         break;
                     }
                 }else { self.advance();
                 }
+            }else if ch == '\\' && self.is_line_continuation() {
+                // jonction explicite avec '\' en fin de ligne
+                self.advance();
+                if self.peek_char() == Some('\r') {
+                    self.advance();
+                }
+                self.advance();
             }else {
                 break;
             }
@@ -774,6 +940,30 @@ impl<'a> Lexer<'a> {
 
     }
 
+    /// Verifie si le '\' courant est suivi d'une fin de ligne
+    fn is_line_continuation(&mut self) -> bool {
+        match self.peek_next_char() {
+            Some('\n') => true,
+            Some('\r') => self.source.clone().nth(2) == Some('\n'),
+            _ => false,
+        }
+    }
+
+    /// Met a jour la profondeur d'imbrication des delimiteurs ouvrants/fermants
+    fn update_nesting(&mut self, token: &TokenType) {
+        match token {
+            TokenType::DELIMITER(Delimiters::LPAR)
+            | TokenType::DELIMITER(Delimiters::LSBRACKET)
+            | TokenType::DELIMITER(Delimiters::LCURBRACE) => self.nesting += 1,
+            TokenType::DELIMITER(Delimiters::RPAR)
+            | TokenType::DELIMITER(Delimiters::RSBRACKET)
+            | TokenType::DELIMITER(Delimiters::RCURBRACE) => {
+                self.nesting = self.nesting.saturating_sub(1)
+            }
+            _ => {}
+        }
+    }
+
     /// C'est la deuxième methode principal avec get_token() pour obtenir les tokens
     /// Son role c'est de tokeniser le code source
     /// appel la methode get_token pour obtenir les tokens.
@@ -781,34 +971,21 @@ impl<'a> Lexer<'a> {
     /// elle retourne un vecteur de tokens Vec<Token>
     /// methode pour tokeniser le code source
     pub fn tokenize(&mut self) -> Vec<Token> {
-        let mut tokens = Vec::new();
-        while let Some(token_type) = self.get_token() {
-            let token = Token::new(
-                self.current_token_text.clone(),
-                token_type.clone(),
-                self.current_line,
-                self.current_column,
-            );
-            tokens.push(token);
-            self.current_token_text.clear();
-            if matches!(token_type, TokenType::EOF) {
-                break;
-            }
-        }
+        self.by_ref().collect()
+    }
 
-        if self.syntax_mode == SyntaxMode::Indentation {
-            while self.indent_level.len() > 1 {
-                self.indent_level.pop();
-                tokens.push(Token::new(
-                    String::new(),
-                    TokenType::DEDENT,
-                    self.current_line,
-                    self.current_column,
-                ));
+    /// Variante de tokenize qui retire les erreurs du flux de tokens :
+    /// le parser recoit un flux propre et l'appelant recupere toutes les erreurs lexicales
+    pub fn tokenize_with_errors(&mut self) -> (Vec<Token>, Vec<LexerError>) {
+        let mut tokens = Vec::new();
+        let mut errors = Vec::new();
+        for token in self.by_ref() {
+            match token.token_type {
+                TokenType::ERROR(error) => errors.push(error),
+                _ => tokens.push(token),
             }
         }
-
-        return tokens;
+        (tokens, errors)
     }
 
     /// methode pour les differents types de token de Type Unknown
@@ -821,11 +998,14 @@ impl<'a> Lexer<'a> {
 
     /// Methode pour creer un token de type erreur
     fn create_error(&self, error: LexerErrorType) -> TokenType {
-        let position = Position {
+        TokenType::ERROR(LexerError::new(error.clone(), error.to_string(), self.current_position()))
+    }
+
+    fn current_position(&self) -> Position {
+        Position {
             line: self.current_line,
             column: self.current_column,
-        };
-        TokenType::ERROR(LexerError::new(error.clone(), error.to_string(), position))
+        }
     }
 
     fn handle_newline(&mut self) -> TokenType {
@@ -836,6 +1016,46 @@ impl<'a> Lexer<'a> {
 
 }
 
+/// Le lexer produit ses tokens a la demande
+impl Iterator for Lexer<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        if self.finished {
+            // on ferme les blocs encore ouverts apres EOF
+            if self.syntax_mode == SyntaxMode::Indentation && self.indent_level.len() > 1 {
+                self.indent_level.pop();
+                return Some(Token::new(
+                    String::new(),
+                    TokenType::DEDENT,
+                    self.current_line,
+                    self.current_column,
+                ));
+            }
+            return None;
+        }
+
+        let token_type = match self.get_token() {
+            Some(token_type) => token_type,
+            None => {
+                self.finished = true;
+                return self.next();
+            }
+        };
+        if matches!(token_type, TokenType::EOF) {
+            self.finished = true;
+        }
+        let token = Token::new(
+            self.current_token_text.clone(),
+            token_type,
+            self.current_line,
+            self.current_column,
+        );
+        self.current_token_text.clear();
+        Some(token)
+    }
+}
+
 /////////////////////////////////by YmC///////////////////////////////////////////////
 
 
@@ -37,6 +37,10 @@ pub enum LexerErrorType {
     UnterminatedString,
     UnterminatedComment,
     InvaliIndentation,
+    MixedScriptIdentifier(String),
+    ConfusableIdentifier(String, String),
+    MixedTabsAndSpaces,
+    IndentationError { found: usize, expected: Vec<usize> },
 }
 
 #[allow(dead_code)]
@@ -83,6 +87,20 @@ impl Display for LexerErrorType {
             LexerErrorType::UnterminatedString => write!(f, "Unterminated string"),
             LexerErrorType::UnterminatedComment => write!(f, "Unterminated comment"),
             LexerErrorType::InvaliIndentation => write!(f, "Invalid indentation"),
+            LexerErrorType::MixedScriptIdentifier(name) => {
+                write!(f, "Mixed-script identifier: {}", name)
+            }
+            LexerErrorType::ConfusableIdentifier(name, other) => {
+                write!(f, "Identifier {} is confusable with {}", name, other)
+            }
+            LexerErrorType::MixedTabsAndSpaces => {
+                write!(f, "Inconsistent use of tabs and spaces in indentation")
+            }
+            LexerErrorType::IndentationError { found, expected } => write!(
+                f,
+                "Unindent to column {} does not match any outer indentation level {:?}",
+                found, expected
+            ),
         }
     }
 }
@@ -152,6 +170,38 @@ impl LexerError {
             position,
         )
     }
+    pub fn mixed_script_identifier(name: &str, position: Position) -> Self {
+        Self::new(
+            LexerErrorType::MixedScriptIdentifier(name.to_string()),
+            format!("Mixed-script identifier: {}", name),
+            position,
+        )
+    }
+    pub fn mixed_tabs_and_spaces(position: Position) -> Self {
+        Self::new(
+            LexerErrorType::MixedTabsAndSpaces,
+            "Inconsistent use of tabs and spaces in indentation".to_string(),
+            position,
+        )
+    }
+    pub fn indentation_error(found: usize, expected: Vec<usize>, position: Position) -> Self {
+        let message = format!(
+            "Unindent to column {} at line {} does not match any outer indentation level {:?}",
+            found, position.line, expected
+        );
+        Self::new(
+            LexerErrorType::IndentationError { found, expected },
+            message,
+            position,
+        )
+    }
+    pub fn confusable_identifier(name: &str, other: &str, position: Position) -> Self {
+        Self::new(
+            LexerErrorType::ConfusableIdentifier(name.to_string(), other.to_string()),
+            format!("Identifier {} is confusable with {}", name, other),
+            position,
+        )
+    }
 }
 
 //by YmC
@@ -60,7 +60,8 @@ pub enum Operators {
     AND,             // '&&' ET ET / AND
     OR,              // '||' OU OU / OR
 
-    //DOUBLESLASH, // '//' DOUBLE SLASH / DOUBLE SLASH
+    // mode indentation uniquement : en mode accolades '//' ouvre un commentaire
+    DOUBLESLASH,      // '//' DOUBLE SLASH / DOUBLE SLASH
     DOUBLESLASHEQUAL, // '//=' DOUBLE SLASH EGAL / DOUBLE SLASH EQUAL
     AT,               // '@' AROBASE / AT
     ATEQUAL,          // '@=' AROBASE EGAL / AT EQUAL
@@ -110,6 +111,7 @@ pub enum Keywords {
     LOOP,
     MATCH,
     MOD,
+    MOVE,
     MUT,
     NONE,
     NOT,
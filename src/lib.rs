@@ -1,8 +1,10 @@
 pub mod lexer;
 //mod parser;
 mod codegen;
+pub mod modules;
 pub mod parser;
-mod semantic;
+pub mod runtime;
+pub mod semantic;
 mod utils;
 
 //mod ast;
@@ -0,0 +1,508 @@
+//! Chargement d'un programme en plusieurs fichiers.
+//!
+//! Un chemin de module `a.b` correspond au fichier `a/b.pyr` (ou `a/b/mod.pyr`),
+//! cherche d'abord depuis la racine du projet puis dans chaque chemin de recherche.
+//! Chaque module n'est lu et parse qu'une fois ; le graphe des imports est parcouru
+//! en profondeur et un import circulaire est signale avec le cycle complet.
+//!
+//! `use a.b::{x as y}` et `from a.b import x as y` importent des symboles de `a.b` ;
+//! `use a.b` importe le module `a.b` s'il existe, sinon le symbole `b` du module `a`.
+//! `from a.b import *` importe tous les symboles publics de `a.b`, a condition
+//! qu'aucun d'eux n'entre en conflit avec un autre nom du module qui importe.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::lexer::lex::{Lexer, SyntaxMode};
+use crate::lexer::lexer_error::LexerError;
+use crate::lexer::tok::TokenType;
+use crate::parser::ast::{ASTNode, Declaration, Statement, Visibility};
+use crate::parser::parser::Parser;
+use crate::parser::parser_error::ParserError;
+use crate::semantic::visibility::VisibilityChecker;
+
+pub const DEFAULT_EXTENSION: &str = "pyr";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SymbolKind {
+    Function,
+    Constant,
+    Variable,
+    Struct,
+    Class,
+    Enum,
+    Trait,
+    Module,
+}
+
+/// Symbole de premier niveau d'un module, tel que le voit le module qui l'importe
+#[derive(Debug, Clone)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub visibility: Visibility,
+    /// module ou le symbole est declare
+    pub module: String,
+    /// None pour un module importe
+    pub declaration: Option<Declaration>,
+}
+
+#[derive(Debug, Clone)]
+pub enum ImportedItems {
+    /// `use a.b` ou `use a.b as c`
+    Module { alias: Option<String> },
+    /// `use a.b::{x, y as z}` ou `use a.b` quand `b` est un symbole de `a`
+    Symbols(Vec<(String, Option<String>)>),
+    /// `from a.b import *`
+    All,
+}
+
+#[derive(Debug, Clone)]
+pub struct ResolvedImport {
+    pub module: String,
+    pub items: ImportedItems,
+}
+
+#[derive(Debug, Clone)]
+pub struct Module {
+    pub name: String,
+    pub path: PathBuf,
+    pub body: Vec<ASTNode>,
+    pub imports: Vec<ResolvedImport>,
+    pub symbols: HashMap<String, Symbol>,
+}
+
+#[derive(Debug, Clone)]
+pub enum ModuleError {
+    NotFound { module: String, searched: Vec<PathBuf> },
+    Io { path: PathBuf, message: String },
+    Lex { module: String, error: LexerError },
+    Parse { module: String, error: ParserError },
+    CircularImport { cycle: Vec<String> },
+    UnknownSymbol { module: String, symbol: String, importer: String },
+    /// import d'un symbole qui n'est pas `pub`
+    PrivateSymbol { module: String, symbol: String, importer: String },
+    /// acces a un element prive d'un autre module
+    PrivateAccess { module: String, message: String },
+    /// un nom importe par `*` est deja defini ou importe ailleurs dans `importer`
+    ImportConflict { importer: String, name: String, first: String, second: String },
+}
+
+impl Display for ModuleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ModuleError::NotFound { module, searched } => {
+                write!(f, "Module '{}' not found, searched:", module)?;
+                for path in searched {
+                    write!(f, "\n  {}", path.display())?;
+                }
+                Ok(())
+            }
+            ModuleError::Io { path, message } => write!(f, "Cannot read {}: {}", path.display(), message),
+            ModuleError::Lex { module, error } => write!(f, "In module '{}': {}", module, error),
+            ModuleError::Parse { module, error } => write!(f, "In module '{}': {}", module, error),
+            ModuleError::CircularImport { cycle } => write!(f, "Circular import: {}", cycle.join(" -> ")),
+            ModuleError::UnknownSymbol { module, symbol, importer } => write!(
+                f,
+                "Module '{}' has no symbol '{}' (imported by '{}')",
+                module, symbol, importer
+            ),
+            ModuleError::PrivateSymbol { module, symbol, importer } => write!(
+                f,
+                "Symbol '{}' of module '{}' is private (imported by '{}')",
+                symbol, module, importer
+            ),
+            ModuleError::PrivateAccess { module, message } => write!(f, "In module '{}': {}", module, message),
+            ModuleError::ImportConflict { importer, name, first, second } => write!(
+                f,
+                "Name '{}' in module '{}' comes from both '{}' and '{}'",
+                name, importer, first, second
+            ),
+        }
+    }
+}
+
+/// Modules charges, dans l'ordre ou ils doivent etre analyses (dependances d'abord)
+#[derive(Debug, Default)]
+pub struct ModuleGraph {
+    modules: HashMap<String, Module>,
+    order: Vec<String>,
+}
+
+impl ModuleGraph {
+    pub fn get(&self, name: &str) -> Option<&Module> {
+        self.modules.get(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.modules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+
+    /// Modules dans l'ordre topologique : chaque module apres ceux qu'il importe
+    pub fn modules_in_order(&self) -> impl Iterator<Item = &Module> {
+        self.order.iter().map(|name| &self.modules[name])
+    }
+
+    /// Modules importes directement par `name`
+    pub fn dependencies(&self, name: &str) -> Vec<&str> {
+        self.modules
+            .get(name)
+            .map(|module| module.imports.iter().map(|import| import.module.as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Symboles visibles dans `name` grace a ses imports, indexes par leur nom local
+    pub fn imported_symbols(&self, name: &str) -> HashMap<String, Symbol> {
+        let mut symbols = HashMap::new();
+        let Some(module) = self.modules.get(name) else { return symbols };
+
+        for import in &module.imports {
+            match &import.items {
+                ImportedItems::Module { alias } => {
+                    let local = alias
+                        .clone()
+                        .unwrap_or_else(|| import.module.rsplit('.').next().unwrap().to_string());
+                    symbols.insert(local, Symbol {
+                        name: import.module.clone(),
+                        kind: SymbolKind::Module,
+                        visibility: Visibility::Public,
+                        module: import.module.clone(),
+                        declaration: None,
+                    });
+                }
+                ImportedItems::Symbols(items) => {
+                    let exporter = &self.modules[&import.module];
+                    for (symbol, alias) in items {
+                        let local = alias.clone().unwrap_or_else(|| symbol.clone());
+                        symbols.insert(local, exporter.symbols[symbol].clone());
+                    }
+                }
+                ImportedItems::All => {
+                    for symbol in public_symbols(&self.modules[&import.module]) {
+                        symbols.insert(symbol.name.clone(), symbol.clone());
+                    }
+                }
+            }
+        }
+        symbols
+    }
+
+    /// Verifie qu'un module n'accede a aucun element prive, chez lui comme chez ceux qu'il importe
+    pub fn check_visibility(&self, name: &str) -> Result<(), ModuleError> {
+        let Some(module) = self.modules.get(name) else { return Ok(()) };
+        let mut checker = VisibilityChecker::new(name);
+        for (local, symbol) in self.imported_symbols(name) {
+            checker = match &symbol.declaration {
+                Some(declaration) => checker.with_import(&local, &symbol.module, declaration),
+                None => checker.with_module(&local, &symbol.module, &self.modules[&symbol.module].body),
+            };
+        }
+        checker.check(&module.body).map_err(|message| ModuleError::PrivateAccess {
+            module: name.to_string(),
+            message,
+        })
+    }
+}
+
+pub struct ModuleLoader {
+    root: PathBuf,
+    search_paths: Vec<PathBuf>,
+    extension: String,
+    syntax_mode: SyntaxMode,
+    graph: ModuleGraph,
+    // pile des modules en cours de chargement, pour detecter les cycles
+    loading: Vec<String>,
+}
+
+impl ModuleLoader {
+    pub fn new(root: impl Into<PathBuf>, syntax_mode: SyntaxMode) -> Self {
+        ModuleLoader {
+            root: root.into(),
+            search_paths: Vec::new(),
+            extension: DEFAULT_EXTENSION.to_string(),
+            syntax_mode,
+            graph: ModuleGraph::default(),
+            loading: Vec::new(),
+        }
+    }
+
+    /// Ajoute un chemin de recherche, consulte apres la racine du projet
+    pub fn with_search_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.search_paths.push(path.into());
+        self
+    }
+
+    pub fn with_extension(mut self, extension: &str) -> Self {
+        self.extension = extension.to_string();
+        self
+    }
+
+    /// Charge le module d'entree (chemin pointe `a.b`) et tout ce qu'il importe
+    pub fn load(mut self, entry: &str) -> Result<ModuleGraph, ModuleError> {
+        self.load_module(entry)?;
+        Ok(self.graph)
+    }
+
+    fn load_module(&mut self, name: &str) -> Result<(), ModuleError> {
+        if self.graph.modules.contains_key(name) {
+            return Ok(());
+        }
+        if let Some(position) = self.loading.iter().position(|loading| loading == name) {
+            let mut cycle = self.loading[position..].to_vec();
+            cycle.push(name.to_string());
+            return Err(ModuleError::CircularImport { cycle });
+        }
+
+        let path = self.resolve(name)?;
+        let source = fs::read_to_string(&path).map_err(|error| ModuleError::Io {
+            path: path.clone(),
+            message: error.to_string(),
+        })?;
+        let body = self.parse(name, &source)?;
+
+        self.loading.push(name.to_string());
+        let imports = self.load_imports(name, &body);
+        self.loading.pop();
+        let imports = imports?;
+
+        let symbols = collect_symbols(name, &body);
+        self.check_wildcard_conflicts(name, &imports, &symbols)?;
+        self.graph.modules.insert(name.to_string(), Module {
+            name: name.to_string(),
+            path,
+            body,
+            imports,
+            symbols,
+        });
+        self.graph.order.push(name.to_string());
+        Ok(())
+    }
+
+    fn load_imports(&mut self, importer: &str, body: &[ASTNode]) -> Result<Vec<ResolvedImport>, ModuleError> {
+        let mut imports = Vec::new();
+        for node in body {
+            let import = match node {
+                ASTNode::Statement(Statement::ModuleImportStatement(import)) => {
+                    self.load_path_import(importer, &import.module_path, import.alias.clone())?
+                }
+                ASTNode::Statement(Statement::SpecificImportStatement(import)) => {
+                    let module = import.module_path.join(".");
+                    self.load_module(&module)?;
+                    ResolvedImport {
+                        module,
+                        items: ImportedItems::Symbols(import.imports.clone()),
+                    }
+                }
+                ASTNode::Statement(Statement::WildcardImportStatement(import)) => {
+                    let module = import.module_path.join(".");
+                    self.load_module(&module)?;
+                    ResolvedImport {
+                        module,
+                        items: ImportedItems::All,
+                    }
+                }
+                _ => continue,
+            };
+            self.check_imported_symbols(importer, &import)?;
+            imports.push(import);
+        }
+        Ok(imports)
+    }
+
+    /// `use a.b` : le module `a.b` s'il existe, sinon le symbole `b` du module `a`
+    fn load_path_import(&mut self, importer: &str, path: &[String], alias: Option<String>) -> Result<ResolvedImport, ModuleError> {
+        let module = path.join(".");
+        if path.len() > 1 && self.resolve(&module).is_err() {
+            let parent = path[..path.len() - 1].join(".");
+            if self.resolve(&parent).is_ok() {
+                self.load_module(&parent)?;
+                let symbol = path.last().unwrap().clone();
+                let import = ResolvedImport {
+                    module: parent,
+                    items: ImportedItems::Symbols(vec![(symbol, alias)]),
+                };
+                self.check_imported_symbols(importer, &import)?;
+                return Ok(import);
+            }
+        }
+
+        self.load_module(&module)?;
+        Ok(ResolvedImport {
+            module,
+            items: ImportedItems::Module { alias },
+        })
+    }
+
+    fn check_imported_symbols(&self, importer: &str, import: &ResolvedImport) -> Result<(), ModuleError> {
+        let ImportedItems::Symbols(items) = &import.items else { return Ok(()) };
+        let exporter = &self.graph.modules[&import.module];
+        for (symbol, _) in items {
+            match exporter.symbols.get(symbol) {
+                None => {
+                    return Err(ModuleError::UnknownSymbol {
+                        module: import.module.clone(),
+                        symbol: symbol.clone(),
+                        importer: importer.to_string(),
+                    })
+                }
+                Some(exported) if exported.visibility == Visibility::Private => {
+                    return Err(ModuleError::PrivateSymbol {
+                        module: import.module.clone(),
+                        symbol: symbol.clone(),
+                        importer: importer.to_string(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Un nom amene par `*` ne doit ni masquer une declaration locale, ni un autre import
+    fn check_wildcard_conflicts(&self, importer: &str, imports: &[ResolvedImport], locals: &HashMap<String, Symbol>) -> Result<(), ModuleError> {
+        // nom local -> (module d'origine, nom d'origine, importe par '*')
+        let mut names: HashMap<String, (String, String, bool)> = locals
+            .keys()
+            .map(|name| (name.clone(), (importer.to_string(), name.clone(), false)))
+            .collect();
+
+        for import in imports {
+            let exporter = &self.graph.modules[&import.module];
+            let incoming: Vec<(String, String, bool)> = match &import.items {
+                ImportedItems::Module { .. } => continue,
+                ImportedItems::Symbols(items) => items
+                    .iter()
+                    .map(|(symbol, alias)| (alias.clone().unwrap_or_else(|| symbol.clone()), symbol.clone(), false))
+                    .collect(),
+                ImportedItems::All => public_symbols(exporter)
+                    .map(|symbol| (symbol.name.clone(), symbol.name.clone(), true))
+                    .collect(),
+            };
+
+            for (local, symbol, wildcard) in incoming {
+                let origin = exporter.symbols[&symbol].module.clone();
+                if let Some((first, first_symbol, first_wildcard)) = names.get(&local) {
+                    let same = *first == origin && *first_symbol == symbol;
+                    if !same && (wildcard || *first_wildcard) {
+                        return Err(ModuleError::ImportConflict {
+                            importer: importer.to_string(),
+                            name: local,
+                            first: first.clone(),
+                            second: origin,
+                        });
+                    }
+                }
+                names.insert(local, (origin, symbol, wildcard));
+            }
+        }
+        Ok(())
+    }
+
+    /// Fichier d'un module : `a/b.<ext>` puis `a/b/mod.<ext>`, depuis la racine puis les chemins de recherche
+    fn resolve(&self, name: &str) -> Result<PathBuf, ModuleError> {
+        let relative: PathBuf = name.split('.').collect();
+        let mut searched = Vec::new();
+
+        for base in std::iter::once(&self.root).chain(&self.search_paths) {
+            for candidate in [
+                base.join(&relative).with_extension(&self.extension),
+                base.join(&relative).join("mod").with_extension(&self.extension),
+            ] {
+                if candidate.is_file() {
+                    return Ok(candidate);
+                }
+                searched.push(candidate);
+            }
+        }
+        Err(ModuleError::NotFound {
+            module: name.to_string(),
+            searched,
+        })
+    }
+
+    fn parse(&self, name: &str, source: &str) -> Result<Vec<ASTNode>, ModuleError> {
+        let mut lexer = Lexer::new(source, self.syntax_mode);
+        let (tokens, errors) = lexer.tokenize_with_errors();
+        if let Some(error) = errors.into_iter().next() {
+            return Err(ModuleError::Lex {
+                module: name.to_string(),
+                error,
+            });
+        }
+
+        let mut parser = Parser::new(tokens, self.syntax_mode);
+        let mut body = Vec::new();
+        loop {
+            // lignes vides et fins de ligne laissees par une declaration en mode indentation
+            while parser.match_token(&[TokenType::NEWLINE]) {}
+            if parser.is_at_end() {
+                break;
+            }
+            let statement = parser.parse_statement().map_err(|error| ModuleError::Parse {
+                module: name.to_string(),
+                error,
+            })?;
+            body.push(statement);
+        }
+        Ok(body)
+    }
+}
+
+/// Declarations de premier niveau d'un module
+fn collect_symbols(module: &str, body: &[ASTNode]) -> HashMap<String, Symbol> {
+    let mut symbols = HashMap::new();
+    for node in body {
+        let declaration = match node {
+            ASTNode::Declaration(declaration) => declaration,
+            ASTNode::Statement(Statement::DeclarationStatement(declaration)) => declaration,
+            _ => continue,
+        };
+        let (name, kind, visibility) = match declaration {
+            Declaration::Function(function) => (&function.name, SymbolKind::Function, function.visibility.clone()),
+            Declaration::Constante(constant) => (&constant.name, SymbolKind::Constant, constant.visibility.clone()),
+            Declaration::Variable(variable) => (&variable.name, SymbolKind::Variable, Visibility::Private),
+            Declaration::Structure(structure) => (&structure.name, SymbolKind::Struct, structure.visibility.clone()),
+            Declaration::Class(class) => (&class.name, SymbolKind::Class, class.visibility.clone()),
+            Declaration::Enum(enumeration) => (&enumeration.name, SymbolKind::Enum, enumeration.visibility.clone()),
+            Declaration::Trait(trait_declaration) => (&trait_declaration.name, SymbolKind::Trait, trait_declaration.visibility.clone()),
+            Declaration::Module(module) => (&module.name, SymbolKind::Module, module.visibility.clone()),
+            _ => continue,
+        };
+        symbols.insert(name.clone(), Symbol {
+            name: name.clone(),
+            kind,
+            visibility,
+            module: module.to_string(),
+            declaration: Some(declaration.clone()),
+        });
+    }
+    symbols
+}
+
+fn public_symbols(module: &Module) -> impl Iterator<Item = &Symbol> {
+    module
+        .symbols
+        .values()
+        .filter(|symbol| symbol.visibility == Visibility::Public)
+}
+
+/// Nom de module d'un fichier relatif a la racine : `a/b.pyr` -> `a.b`
+pub fn module_name(root: &Path, file: &Path) -> Option<String> {
+    let relative = file.strip_prefix(root).ok()?.with_extension("");
+    let segments: Vec<String> = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    if segments.is_empty() {
+        None
+    } else {
+        Some(segments.join("."))
+    }
+}
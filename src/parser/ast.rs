@@ -119,6 +119,17 @@ pub enum Operator {
     GreaterThanOrEqual, // >=
     Range, // ..
     RangeInclusive, // ..=
+    In,     // in
+    NotIn,  // not in
+    Is,     // is
+    IsNot,  // is not
+    BitwiseAnd,     // &
+    BitwiseOr,      // |
+    BitwiseXor,     // ^
+    LeftShift,      // <<
+    RightShift,     // >>
+    Power,          // **
+    FloorDivision,  // //
 
 }
 
@@ -150,6 +161,8 @@ pub enum CompoundOperator{
     BitwiseXorAssign,   // ^=
     LeftShiftAssign,    // <<=
     RightShiftAssign,   // >>=
+    PowAssign,          // **=
+    FloorDivAssign,     // //=
 }
 
 
@@ -184,6 +197,7 @@ pub enum Type {
     Char,
     Array(Box<Type>),
     Tuple(Vec<Type>),
+    Function(Vec<Type>, Box<Type>), // fn(int) -> int : fonction ou fermeture
     Custom(String),
     Generic(GenericType),
     Infer, // Type inféré déduire par le compilateur (Type Inference)
@@ -214,6 +228,7 @@ pub enum Declaration {
     Trait(TraitDeclaration),
     Impl(ImplDeclaration),
     Module(ModuleDeclaration),
+    Destructuring(DestructuringDeclaration),
     Macro(MacroDeclaration),
     Attributes(Attribute),
     Constructor(Constructor),
@@ -228,6 +243,15 @@ pub struct VariableDeclaration {
     pub value: Option<Expression>,
     pub mutability: Mutability,
 }
+/// `let (a, mut b) = f()` : chaque nom du motif devient une variable
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct DestructuringDeclaration {
+    pub pattern: Pattern,
+    pub variable_type: Option<Type>,
+    pub value: Expression,
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct FunctionDeclaration {
@@ -235,7 +259,8 @@ pub struct FunctionDeclaration {
     pub parameters: Vec<Parameter>, // (nom, type)
     pub return_type: Option<Type>,
     pub body: Vec<ASTNode>,
-    pub visibility: Visibility
+    pub visibility: Visibility,
+    pub is_async: bool, // async fn : l'appel renvoie un Future<return_type>
     //pub annotations: Vec<Annotation>,
 }
 #[allow(dead_code)]
@@ -293,8 +318,17 @@ pub struct MethodeDeclaration {
 #[derive(Debug, Clone)]
 pub struct Parameter{
     pub name: String,
-    pub parameter_type: Type,
+    pub parameter_type: Type, // pour `*args: T`, type de chaque element
+    pub default_value: Option<Expression>, // port: int = 80
+    pub kind: ParameterKind,
+}
 
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterKind {
+    Regular,
+    Variadic,        // *args : arguments positionnels restants, Array<T>
+    KeywordVariadic, // **kwargs : arguments nommes restants, Dict<str, T>
 }
 
 #[allow(dead_code)]
@@ -354,7 +388,8 @@ pub struct ImplDeclaration {
 #[derive(Debug, Clone)]
 pub struct ModuleDeclaration {
     pub name: String,
-    pub statements: Vec<Statement>,
+    pub body: Vec<ASTNode>,
+    pub visibility: Visibility,
 }
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -457,22 +492,29 @@ pub enum Expression {
     Literal(Literal),
     Identifier(String),
     BinaryOperation(BinaryOperation),
+    ChainedComparison(ChainedComparison),
     UnaryOperation(UnaryOperation),
     FunctionCall(FunctionCall),
 
     ArrayAccess(ArrayAccess), // transfere dans IndexAccess
-    ArraySlice(ArraySlice), // pas encore completement implementé
-    Slice(Slice), // pas encore completement implementé
-    RangeSlice(RangeSlice), // pas encore completement implementé
+    ArraySlice(ArraySlice), // a[1:5:2], a[..3], a[2..=4]
 
 
     MemberAccess(MemberAccess),
+    StructLiteral(StructLiteral),
+    Tuple(TupleExpression),
+    TupleAccess(TupleAccess),
     LambdaExpression(LambdaExpression),
     MatchExpression(MatchExpression),
     MatchArm(Box<MatchArm>),
     TypeCast(TypeCast),
+    Try(TryExpression),
+    Await(AwaitExpression),
     Conditional(Conditional),
+    Block(BlockExpression),
     Assignment(Assignment),
+    NamedExpression(NamedExpression),
+    LetCondition(LetCondition),
     Borrow(Borrow),
     Statement(Box<Statement>),
     MethodCall(MethodCall),
@@ -582,6 +624,7 @@ pub struct MethodCall{
     pub object: Box<Expression>,
     pub method: String,
     pub arguments: Vec<Expression>,
+    pub keyword_arguments: Vec<KeywordArgument>,
 }
 
 #[allow(dead_code)]
@@ -665,12 +708,31 @@ pub struct BinaryOperation {
     pub right: Box<Expression>,
 }
 
+/// Chaine de comparaisons a la Python : `a < b <= c` vaut `a < b and b <= c`,
+/// chaque operande n'etant evalue qu'une seule fois.
+/// `operands` contient toujours un element de plus que `operators`.
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct ChainedComparison {
+    pub operands: Vec<Expression>,
+    pub operators: Vec<Operator>,
+}
+
 
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct FunctionCall {
     pub name: Box<Expression>,
     pub arguments: Vec<Expression>,
+    pub keyword_arguments: Vec<KeywordArgument>, // connect(host="x", port=8080)
+}
+
+/// Argument nomme `nom=valeur` ; toujours apres les arguments positionnels
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct KeywordArgument {
+    pub name: String,
+    pub value: Expression,
 }
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -679,6 +741,8 @@ pub struct ArrayAccess {
     pub index: Box<Expression>,
 }
 
+/// Tranche `a[debut:fin:pas]` ou `a[debut..fin]` / `a[debut..=fin]`.
+/// Bornes negatives comptees depuis la fin, bornes absentes = extremites (voir runtime::slice)
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct ArraySlice{
@@ -686,23 +750,38 @@ pub struct ArraySlice{
     pub start: Option<Box<Expression>>,
     pub end: Option<Box<Expression>>,
     pub step: Option<Box<Expression>>,
-    // pub inclusive: bool,
+    pub inclusive: bool, // fin incluse : a[2..=4]
+}
+
+/// `(1, "a")`, `(x,)` ou `()`
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct TupleExpression {
+    pub elements: Vec<Expression>,
 }
 
+/// `t.0`
+#[allow(dead_code)]
 #[derive(Debug, Clone)]
-pub struct Slice {
-    pub start: Option<Box<Expression>>,
-    pub end: Option<Box<Expression>>,
-    pub step: Option<Box<Expression>>
+pub struct TupleAccess {
+    pub tuple: Box<Expression>,
+    pub index: usize,
 }
 
+/// `Point { x: 1, y }` ou `Point { x: 0, ..p }` : les champs absents sont copies de `base`
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct StructLiteral {
+    pub name: String,
+    pub fields: Vec<FieldInit>,
+    pub base: Option<Box<Expression>>,
+}
 
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
-pub struct RangeSlice{
-    pub array: Box<Expression>,
-    pub range: Box<Expression>,
-    pub step: Option<Box<Expression>>,
+pub struct FieldInit {
+    pub name: String,
+    pub value: Expression, // `x` seul est note `x: x`
 }
 
 #[allow(dead_code)]
@@ -719,6 +798,37 @@ pub struct TypeCast {
     pub target_type: Type,
 }
 
+/// `await expr` : suspend la fonction async jusqu'a ce que le Future soit pret
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct AwaitExpression {
+    pub expression: Box<Expression>,
+}
+
+/// Expression d'assignation `(n := len(items))` : lie `name` dans la portee
+/// de la fonction englobante (meme depuis une comprehension) et vaut `value`
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct NamedExpression {
+    pub name: String,
+    pub value: Box<Expression>,
+}
+
+/// Condition `let motif = valeur` d'un `if let` / `while let` : vraie si la valeur correspond au motif
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct LetCondition {
+    pub pattern: Pattern,
+    pub value: Box<Expression>,
+}
+
+/// Propagation d'erreur `expr?` sur un `Option` ou un `Result`
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct TryExpression {
+    pub expression: Box<Expression>,
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct Conditional {
@@ -727,6 +837,13 @@ pub struct Conditional {
     pub else_block: Box<Expression>,
 }
 
+/// bloc { ... } utilise comme expression: sa valeur est celle de la derniere expression
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct BlockExpression {
+    pub statements: Vec<ASTNode>,
+}
+
 
 #[allow(dead_code)]
 #[derive(Clone, Debug)]
@@ -739,6 +856,7 @@ pub enum Statement {
 
     ModuleImportStatement(ModuleImportStatement),
     SpecificImportStatement(SpecificImportStatement),
+    WildcardImportStatement(WildcardImportStatement),
 
 
     RaiseStatement(RaiseStatement),
@@ -784,11 +902,20 @@ pub struct SpecificImportStatement{
     pub imports : Vec<(String,Option<String>)>
 }
 
+/// `from a.b import *` : importe tous les symboles publics du module
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct WildcardImportStatement{
+    pub keyword: ImportKeyword,
+    pub module_path: Vec<String>,
+}
+
 #[allow(dead_code)]
 #[derive(Clone, Debug)]
 pub enum ImportKeyword{
     Use,
     Import,
+    From,
 }
 
 #[allow(dead_code)]
@@ -897,12 +1024,13 @@ pub struct ImportStatement {
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct RaiseStatement {
-    pub exception: Expression,
+    pub exception: Option<Expression>,  // None pour re-lever l'exception courante: 'raise'
+    pub cause: Option<Expression>,      // pour raise Err from cause
 }
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct DelStatement {
-    pub target: Expression,
+    pub targets: Vec<Expression>,
 }
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -925,13 +1053,21 @@ pub struct ExceptHandler{
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct WithStatement {
-    pub target: Expression,
-    pub body: Body,
+    pub items: Vec<WithItem>,
+    pub body: Vec<ASTNode>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct WithItem {
+    pub context: Expression,
+    pub alias: Option<String>,  // pour with expr as alias
 }
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct YieldStatement {
     pub value: Option<Expression>,
+    pub from: bool, // yield from iterable : produit chaque element de value
 }
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -961,6 +1097,24 @@ pub struct LambdaExpression {
     //pub body: Box<Expression>,
     pub body: Vec<ASTNode>,
     //pub body: Body,
+    pub is_move: bool, // `move |x| ...`
+    pub captures: Vec<Capture>, // rempli par l'analyse semantique (semantic::closures)
+}
+
+/// Variable de la portee englobante utilisee par une fermeture
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Capture {
+    pub name: String,
+    pub mode: CaptureMode,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CaptureMode {
+    Reference,
+    MutableReference,
+    Move,
 }
 
 #[allow(dead_code)]
@@ -1004,6 +1158,7 @@ pub struct MatchArm {
 pub enum Pattern {
     Literal(Literal),
     Identifier(String),
+    MutableIdentifier(String), // `mut x`, dans un motif de `let`
     Wildcard,
     EnumVariant(EnumVariant),
     Tuple(Vec<Pattern>),
@@ -1013,6 +1168,24 @@ pub enum Pattern {
     TupleRest(Vec<Pattern>),
     ArrayRest(ArrayRest),
     RangePattern(RangePattern),
+    Or(Vec<Pattern>),                // `1 | 2 | 3`
+    Binding(String, Box<Pattern>),   // `n @ 1..=9` ; `..rest` est note `rest @ ..`
+    Struct(StructPattern),           // `Point { x, y: 0, .. }`
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct StructPattern {
+    pub name: String,
+    pub fields: Vec<FieldPattern>,
+    pub has_rest: bool, // `..` : les autres champs sont ignores
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct FieldPattern {
+    pub name: String,
+    pub pattern: Pattern, // `x` seul est note `x: x`
 }
 
 #[allow(dead_code)]
@@ -0,0 +1,309 @@
+//! Arbre syntaxique concret (CST) sans perte.
+//!
+//! Chaque token garde le texte exact du source ainsi que ses trivia
+//! (espaces, retours a la ligne, commentaires) avant et apres lui.
+//! La concatenation de tous les tokens redonne le source a l'identique,
+//! ce qui permet aux outils (formatter, refactorings, code actions)
+//! de reecrire le code sans perdre la mise en forme de l'utilisateur.
+//! L'ASTNode habituel se derive du CST via `CstNode::to_ast`.
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::lexer::lex::{Lexer, SyntaxMode, Token};
+use crate::parser::ast::ASTNode;
+use crate::parser::parser::Parser;
+use crate::parser::parser_error::ParserError;
+use crate::tok::{Delimiters, TokenType};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TriviaKind {
+    Whitespace,
+    Newline,
+    Comment,
+    LineContinuation,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub text: String,
+}
+
+/// Token du CST : le token du lexer, son texte source exact et ses trivia
+#[derive(Debug, Clone)]
+pub struct CstToken {
+    pub token: Token,
+    pub text: String,
+    pub leading_trivia: Vec<Trivia>,
+    pub trailing_trivia: Vec<Trivia>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CstNodeKind {
+    Root,
+    /// bloc indente, de INDENT a DEDENT
+    Block,
+    /// groupe entre delimiteurs : (), [] ou {}
+    Group,
+}
+
+#[derive(Debug, Clone)]
+pub enum CstElement {
+    Node(CstNode),
+    Token(CstToken),
+}
+
+#[derive(Debug, Clone)]
+pub struct CstNode {
+    pub kind: CstNodeKind,
+    pub children: Vec<CstElement>,
+}
+
+impl CstToken {
+    fn write_to(&self, out: &mut String) {
+        for trivia in &self.leading_trivia {
+            out.push_str(&trivia.text);
+        }
+        out.push_str(&self.text);
+        for trivia in &self.trailing_trivia {
+            out.push_str(&trivia.text);
+        }
+    }
+}
+
+impl CstNode {
+    fn new(kind: CstNodeKind) -> Self {
+        CstNode {
+            kind,
+            children: Vec::new(),
+        }
+    }
+
+    /// Construit le CST d'un code source
+    pub fn parse(source: &str, syntax_mode: SyntaxMode) -> CstNode {
+        let tokens = lex_with_trivia(source, syntax_mode);
+        build_tree(tokens)
+    }
+
+    /// Texte source exact couvert par ce noeud
+    pub fn text(&self) -> String {
+        let mut out = String::new();
+        for token in self.tokens() {
+            token.write_to(&mut out);
+        }
+        out
+    }
+
+    /// Tous les tokens du noeud, dans l'ordre du source
+    pub fn tokens(&self) -> Vec<&CstToken> {
+        let mut tokens = Vec::new();
+        for child in &self.children {
+            match child {
+                CstElement::Token(token) => tokens.push(token),
+                CstElement::Node(node) => tokens.extend(node.tokens()),
+            }
+        }
+        tokens
+    }
+
+    /// Flux de tokens sans trivia, tel que l'attend le parser
+    pub fn to_tokens(&self) -> Vec<Token> {
+        self.tokens()
+            .into_iter()
+            .map(|token| token.token.clone())
+            .collect()
+    }
+
+    /// Derive l'AST a partir du CST
+    pub fn to_ast(&self, syntax_mode: SyntaxMode) -> Result<ASTNode, ParserError> {
+        let mut parser = Parser::new(self.to_tokens(), syntax_mode);
+        parser.parse_program()
+    }
+}
+
+impl Display for CstNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text())
+    }
+}
+
+fn is_layout(token_type: &TokenType) -> bool {
+    matches!(
+        token_type,
+        TokenType::INDENT | TokenType::DEDENT | TokenType::EOF
+    )
+}
+
+/// Decoupe un morceau de source entre deux tokens en trivia
+fn split_trivia(text: &str) -> Vec<Trivia> {
+    let mut trivia: Vec<Trivia> = Vec::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((start, ch)) = chars.next() {
+        let (kind, end) = match ch {
+            '\n' => (TriviaKind::Newline, start + 1),
+            '\r' if matches!(chars.peek(), Some((_, '\n'))) => {
+                chars.next();
+                (TriviaKind::Newline, start + 2)
+            }
+            '\\' => {
+                let mut end = start + 1;
+                while let Some(&(index, next)) = chars.peek() {
+                    chars.next();
+                    end = index + next.len_utf8();
+                    if next == '\n' {
+                        break;
+                    }
+                }
+                (TriviaKind::LineContinuation, end)
+            }
+            _ => {
+                let mut end = start + ch.len_utf8();
+                while let Some(&(index, next)) = chars.peek() {
+                    if next == '\n' || next == '\\' || (next == '\r' && text[index..].starts_with("\r\n")) {
+                        break;
+                    }
+                    chars.next();
+                    end = index + next.len_utf8();
+                }
+                (TriviaKind::Whitespace, end)
+            }
+        };
+        trivia.push(Trivia {
+            kind,
+            text: text[start..end].to_string(),
+        });
+    }
+    trivia
+}
+
+/// Un commentaire '#' ou '//' consomme sa fin de ligne : on la separe en trivia Newline
+fn comment_trivia(text: &str) -> Vec<Trivia> {
+    let (comment, newline) = match text.strip_suffix('\n') {
+        Some(comment) => (comment, Some("\n")),
+        None => (text, None),
+    };
+    let mut trivia = vec![Trivia {
+        kind: TriviaKind::Comment,
+        text: comment.to_string(),
+    }];
+    if let Some(newline) = newline {
+        trivia.push(Trivia {
+            kind: TriviaKind::Newline,
+            text: newline.to_string(),
+        });
+    }
+    trivia
+}
+
+/// Lance le lexer et rattache a chaque token significatif les trivia qui l'entourent.
+/// Les trivia jusqu'a la fin de ligne vont au token precedent (trailing),
+/// le reste au token suivant (leading).
+fn lex_with_trivia(source: &str, syntax_mode: SyntaxMode) -> Vec<CstToken> {
+    let mut lexer = Lexer::new(source, syntax_mode);
+    let mut tokens: Vec<CstToken> = Vec::new();
+    let mut pending: Vec<Trivia> = Vec::new();
+    let mut last_end = 0;
+
+    while let Some(token) = lexer.next() {
+        let span = lexer.token_span();
+        let (start, end) = if is_layout(&token.token_type) {
+            (last_end.max(span.end), last_end.max(span.end))
+        } else {
+            (span.start.max(last_end), span.end.max(last_end))
+        };
+
+        pending.extend(split_trivia(&source[last_end..start]));
+        last_end = end;
+
+        if matches!(token.token_type, TokenType::COMMENT(_)) {
+            pending.extend(comment_trivia(&source[start..end]));
+            continue;
+        }
+
+        let mut leading = Vec::new();
+        let mut pieces = pending.drain(..);
+        if let Some(previous) = tokens.last_mut() {
+            if !matches!(previous.token.token_type, TokenType::NEWLINE) {
+                for piece in pieces.by_ref() {
+                    if piece.kind == TriviaKind::Newline {
+                        leading.push(piece);
+                        break;
+                    }
+                    previous.trailing_trivia.push(piece);
+                }
+            }
+        }
+        leading.extend(pieces);
+
+        tokens.push(CstToken {
+            token,
+            text: source[start..end].to_string(),
+            leading_trivia: leading,
+            trailing_trivia: Vec::new(),
+        });
+    }
+
+    // trivia restantes apres le dernier token
+    pending.extend(split_trivia(&source[last_end..]));
+    match tokens.last_mut() {
+        Some(last) => last.trailing_trivia.extend(pending),
+        None => tokens.push(CstToken {
+            token: Token::new(String::new(), TokenType::EOF, 1, 1),
+            text: String::new(),
+            leading_trivia: pending,
+            trailing_trivia: Vec::new(),
+        }),
+    }
+    tokens
+}
+
+/// Regroupe les tokens en blocs indentes et en groupes de delimiteurs
+fn build_tree(tokens: Vec<CstToken>) -> CstNode {
+    let mut stack = vec![CstNode::new(CstNodeKind::Root)];
+
+    for token in tokens {
+        match &token.token.token_type {
+            TokenType::INDENT => {
+                let mut block = CstNode::new(CstNodeKind::Block);
+                block.children.push(CstElement::Token(token));
+                stack.push(block);
+            }
+            TokenType::DELIMITER(Delimiters::LPAR)
+            | TokenType::DELIMITER(Delimiters::LSBRACKET)
+            | TokenType::DELIMITER(Delimiters::LCURBRACE) => {
+                let mut group = CstNode::new(CstNodeKind::Group);
+                group.children.push(CstElement::Token(token));
+                stack.push(group);
+            }
+            TokenType::DEDENT => close_node(&mut stack, CstNodeKind::Block, token),
+            TokenType::DELIMITER(Delimiters::RPAR)
+            | TokenType::DELIMITER(Delimiters::RSBRACKET)
+            | TokenType::DELIMITER(Delimiters::RCURBRACE) => {
+                close_node(&mut stack, CstNodeKind::Group, token)
+            }
+            _ => stack
+                .last_mut()
+                .unwrap()
+                .children
+                .push(CstElement::Token(token)),
+        }
+    }
+
+    // noeuds non fermes (source invalide) : on les rattache quand meme
+    while stack.len() > 1 {
+        let node = stack.pop().unwrap();
+        stack.last_mut().unwrap().children.push(CstElement::Node(node));
+    }
+    stack.pop().unwrap()
+}
+
+fn close_node(stack: &mut Vec<CstNode>, kind: CstNodeKind, token: CstToken) {
+    let current = stack.last_mut().unwrap();
+    current.children.push(CstElement::Token(token));
+    if stack.len() > 1 && stack.last().unwrap().kind == kind {
+        let node = stack.pop().unwrap();
+        stack.last_mut().unwrap().children.push(CstElement::Node(node));
+    }
+}
@@ -1,6 +1,6 @@
 use crate::lexer::lex::Token;
-use crate::parser::ast::{ArrayAccess, ArrayExpression, ArrayRepeatExpression, ASTNode, Attribute, ClassDeclaration, CompFor, ComprehensionFor, ConstDeclaration, Constructor, Declaration, DictAccess, DictComprehension, DictEntry, DictLiteral, EnumDeclaration, EnumVariant, Expression, Field, FunctionDeclaration, GenericType, ImplDeclaration, ListComprehension, MethodeDeclaration, Mutability, StructDeclaration, TraitDeclaration, TraitMethod, Type, VariableDeclaration, Visibility, WhereClause};
-use crate::parser::ast::Declaration::Variable;
+use crate::parser::ast::{ArrayAccess, ArrayExpression, ArrayRepeatExpression, ASTNode, Attribute, ClassDeclaration, CompFor, ComprehensionFor, ConstDeclaration, Constructor, Declaration, DictAccess, DictComprehension, DictEntry, DestructuringDeclaration, DictLiteral, EnumDeclaration, EnumVariant, Expression, Field, FunctionDeclaration, GenericType, ImplDeclaration, ListComprehension, MethodeDeclaration, ModuleDeclaration, Mutability, StructDeclaration, TraitDeclaration, TraitMethod, Type, VariableDeclaration, Visibility, WhereClause};
+use crate::parser::ast::Declaration::{Destructuring, Variable};
 use crate::parser::parser::Parser;
 use crate::parser::parser_error::ParserError;
 use crate::parser::parser_error::ParserErrorType::{ExpectColon, MultipleConstructors, UnexpectedToken};
@@ -26,6 +26,9 @@ impl Parser{
         println!("Début du parsing de la déclaration de variable");
 
         self.consume(TokenType::KEYWORD(Keywords::LET))?;
+        if self.check(&[TokenType::DELIMITER(Delimiters::LPAR)]) {
+            return self.parse_destructuring_declaration();
+        }
 
         let mutability = self.parse_mutability()?;
 
@@ -66,6 +69,29 @@ impl Parser{
 
     }
 
+    /// `let (a, mut b): (int, str) = f()` ; `let` est deja consomme
+    pub fn parse_destructuring_declaration(&mut self) -> Result<ASTNode, ParserError> {
+        println!("Début du parsing de la déclaration destructurée");
+        let pattern = self.parse_tuple_pattern()?;
+
+        let variable_type = if self.match_token(&[TokenType::DELIMITER(Delimiters::COLON)]) {
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
+
+        self.consume(TokenType::OPERATOR(Operators::EQUAL))?;
+        let value = self.parse_expression(0)?;
+        self.consume_seperator();
+
+        println!("Fin du parsing de la déclaration destructurée OK!!!!!!!!!!!!!!");
+        Ok(ASTNode::Declaration(Destructuring(DestructuringDeclaration {
+            pattern,
+            variable_type,
+            value,
+        })))
+    }
+
     pub fn parse_const_declaration(&mut self, visibility: Visibility) -> Result<ASTNode, ParserError> {
         println!("Début du parsing de la déclaration de constante");
 
@@ -105,6 +131,7 @@ impl Parser{
 
     pub fn parse_function_declaration(&mut self, visibility: Visibility) -> Result<ASTNode, ParserError> {
         println!("Début du parsing de la déclaration de fonction");
+        let is_async = self.match_token(&[TokenType::KEYWORD(Keywords::ASYNC)]);
         self.consume(TokenType::KEYWORD(Keywords::FN))?;
         let name = self.consume_identifier()?;
         println!("Nom de la fonction parsé : {}", name);
@@ -137,6 +164,7 @@ impl Parser{
             return_type: Some(return_type),
             body,
             visibility,
+            is_async,
         })))
     }
 
@@ -173,6 +201,46 @@ impl Parser{
 
 
 
+    /// Module en ligne : `mod nom { ... }` ou `mod nom:` suivi d'un bloc indente.
+    /// Les modules peuvent etre imbriques ; leurs declarations sont privees par defaut.
+    pub fn parse_module_declaration(&mut self, visibility: Visibility) -> Result<ASTNode, ParserError> {
+        println!("Début du parsing de la déclaration de module");
+        self.consume(TokenType::KEYWORD(Keywords::MOD))?;
+        let name = self.consume_identifier()?;
+        println!("Nom du module parsé : {}", name);
+
+        let end = match self.syntax_mode {
+            SyntaxMode::Braces => {
+                self.consume(TokenType::DELIMITER(Delimiters::LCURBRACE))?;
+                TokenType::DELIMITER(Delimiters::RCURBRACE)
+            }
+            SyntaxMode::Indentation => {
+                self.consume(TokenType::DELIMITER(Delimiters::COLON))?;
+                self.consume(TokenType::NEWLINE)?;
+                self.consume(TokenType::INDENT)?;
+                TokenType::DEDENT
+            }
+        };
+
+        let mut body = Vec::new();
+        loop {
+            // fins de ligne laissees apres une declaration
+            while self.match_token(&[TokenType::NEWLINE]) {}
+            if self.check(&[end.clone(), TokenType::EOF]) {
+                break;
+            }
+            body.push(self.parse_statement()?);
+        }
+        self.consume(end)?;
+
+        println!("Fin du parsing de la déclaration de module OK!!!!!!!!!!!!!!");
+        Ok(ASTNode::Declaration(Declaration::Module(ModuleDeclaration{
+            name,
+            body,
+            visibility,
+        })))
+    }
+
     pub fn parse_enum_declaration(&mut self, visibility: Visibility) -> Result<ASTNode, ParserError> {
         println!("Debut du parsing de la déclaration d'énumération");
         self.consume(TokenType::KEYWORD(Keywords::ENUM))?;
@@ -942,14 +1010,19 @@ impl Parser{
         if self.check(&[TokenType::DELIMITER(Delimiters::LSBRACKET)]) {
             self.advance();
 
-            // Chercher un 'for' après une expression
+            // Chercher un 'for' après une expression, hors des crochets imbriques (`[d[k] for k in ks]`)
+            let mut depth = 0;
             while let Some(token) = self.current_token() {
-                if matches!(token.token_type, TokenType::KEYWORD(Keywords::FOR)) {
-                    is_comprehension = true;
-                    break;
-                }
-                if matches!(token.token_type, TokenType::DELIMITER(Delimiters::RSBRACKET)) {
-                    break;
+                match token.token_type {
+                    TokenType::KEYWORD(Keywords::FOR) if depth == 0 => {
+                        is_comprehension = true;
+                        break;
+                    }
+                    TokenType::DELIMITER(Delimiters::LSBRACKET) => depth += 1,
+                    TokenType::DELIMITER(Delimiters::RSBRACKET) if depth == 0 => break,
+                    TokenType::DELIMITER(Delimiters::RSBRACKET) => depth -= 1,
+                    TokenType::EOF => break,
+                    _ => {}
                 }
                 self.advance();
             }
@@ -1,8 +1,9 @@
-use crate::parser::ast::{ ArrayExpression, ArraySlice, Assignment, ASTNode, BinaryOperation, CompoundAssignment, DestructuringAssignment, DictAccess, Expression, FunctionCall, IndexAccess, LambdaExpression, Literal, MemberAccess, MethodCall, Operator, Parameter, RangeExpression, Type, UnaryOperation, UnaryOperator};
+use crate::parser::ast::{ ArrayExpression, ArraySlice, Assignment, ASTNode, AwaitExpression, BinaryOperation, BlockExpression, ChainedComparison, CollectionAccess, CompoundAssignment, Conditional, DestructuringAssignment, Expression, FieldInit, FunctionCall, LambdaExpression, Literal, MemberAccess, KeywordArgument, LetCondition, MethodCall, NamedExpression, Operator, Parameter, ParameterKind, RangeExpression, StructLiteral, TryExpression, TupleAccess, TupleExpression, Type, TypeCast, UnaryOperation, UnaryOperator};
 use crate::parser::parser::Parser;
 use crate::parser::parser_error::ParserError;
-use crate::parser::parser_error::ParserErrorType::{ExpectedArrowOrBlock, ExpectedCloseParenthesis, ExpectedCommaOrClosingParenthesis, UnexpectedEndOfInput, UnexpectedToken};
+use crate::parser::parser_error::ParserErrorType::{ExpectedArrowOrBlock, ExpectedCloseParenthesis, MissingElseBranch, ExpectedCommaOrClosingParenthesis, ExpectedCommaOrCloseBrace, InvalidAssignmentTarget, InvalidSlice, PositionalArgumentAfterKeyword, UnexpectedEndOfInput, UnexpectedToken};
 use crate::tok::{Delimiters, Keywords, Operators, TokenType};
+use crate::SyntaxMode;
 
 impl Parser {
     /// fonction pour parser les expressions
@@ -20,21 +21,23 @@ impl Parser {
         }
 
         if self.check(&[TokenType::DELIMITER(Delimiters::LSBRACKET)]) {
-            // Si nous avons un token précédent et que c'est un '=', alors c'est une expression de tableau
-            // Sinon, c'est une destructuration
-            match self.previous_token() {
-                Some(token) if token.token_type == TokenType::OPERATOR(Operators::EQUAL) => {
-                    return self.parse_array_expression();
-                },
-                _ => {
-                    return self.parse_destructuring_assignment();
-                }
-            }
+            // En debut d'instruction, c'est une destructuration: [a, b] = point
+            // Sinon (apres '=', un operateur, '(' , ',' ...), c'est une expression de tableau
+            return match self.previous_token().map(|token| &token.token_type) {
+                None
+                | Some(TokenType::NEWLINE)
+                | Some(TokenType::INDENT)
+                | Some(TokenType::DEDENT)
+                | Some(TokenType::DELIMITER(Delimiters::SEMICOLON))
+                | Some(TokenType::DELIMITER(Delimiters::LCURBRACE))
+                | Some(TokenType::DELIMITER(Delimiters::RCURBRACE)) => self.parse_destructuring_assignment(),
+                _ => self.parse_array_expression(),
+            };
         }
 
 
         //let mut left = self.parse_postfix_expression()?;
-        let mut left = self.parse_unary_expression()?;
+        let mut left = self.parse_cast_expression()?;
 
 
         if let Some(token) = self.current_token(){
@@ -42,11 +45,30 @@ impl Parser {
                 TokenType::OPERATOR(Operators::EQUAL) => {
                     self.advance();
                     let value = self.parse_expression(precedence)?;
+                    // (a, b) = (b, a)
+                    if let Expression::Tuple(tuple) = left {
+                        return Ok(Expression::DestructuringAssignment(DestructuringAssignment {
+                            targets: tuple.elements,
+                            value: Box::new(value),
+                        }));
+                    }
                     return Ok(Expression::Assignment(Assignment{
                         target: Box::new(left),
                         value: Box::new(value),
                     }));
                 }
+                // expression d'assignation (walrus) : if (n := len(items)) > 10
+                TokenType::OPERATOR(Operators::COLONEQUAL) if precedence == 0 => {
+                    let Expression::Identifier(name) = left else {
+                        return Err(ParserError::new(InvalidAssignmentTarget, self.current_position()));
+                    };
+                    self.advance();
+                    let value = self.parse_expression(0)?;
+                    return Ok(Expression::NamedExpression(NamedExpression{
+                        name,
+                        value: Box::new(value),
+                    }));
+                }
                 TokenType::OPERATOR(op) => {
                     if let Some(compound_op) = self.get_compound_operator(op){
                         self.advance();
@@ -70,8 +92,21 @@ impl Parser {
             }
 
             self.advance();
-            let right = self.parse_expression(precedence +1)?;
+            // 'not in' et 'is not' sont sur deux tokens
+            if let Operator::NotIn | Operator::IsNot = operator {
+                self.advance();
+            }
+            let right_precedence = if self.is_right_associative(&operator) {
+                operator_precedence
+            } else {
+                operator_precedence + 1
+            };
+            let right = self.parse_expression(right_precedence)?;
 
+            if self.is_comparison_operator(&operator) {
+                left = self.parse_comparison_chain(left, operator, right)?;
+                continue;
+            }
 
             if let Operator::Range|Operator::RangeInclusive = operator{
                 left = Expression::RangeExpression(RangeExpression{
@@ -89,6 +124,11 @@ impl Parser {
 
         }
 
+        // expression conditionnelle a la Python: valeur if condition else autre
+        if precedence == 0 && self.check(&[TokenType::KEYWORD(Keywords::IF)]) {
+            left = self.parse_conditional_expression(left)?;
+        }
+
         println!("Fin du parsing de l'expression ");
 
         Ok(left)
@@ -212,6 +252,83 @@ impl Parser {
 
 
 
+    /// Suite de `receveur[` : indexation simple ou tranche.
+    /// Deux formes de tranche, sans melange :
+    /// - a la Python : `a[debut:fin:pas]`, chaque partie optionnelle (`a[:3]`, `a[::-1]`)
+    /// - a la Rust : `a[debut..fin]`, `a[debut..=fin]`, `a[..fin]`, `a[debut..]`
+    fn parse_index_or_slice(&mut self, receiver: Expression) -> Result<Expression, ParserError> {
+        let slice_separators = [
+            TokenType::DELIMITER(Delimiters::COLON),
+            TokenType::DELIMITER(Delimiters::DOUBLECOLON),
+            TokenType::OPERATOR(Operators::DOTDOT),
+            TokenType::OPERATOR(Operators::DOTDOTEQUAL),
+        ];
+
+        // une borne s'arrete avant '..' ; si ce n'est pas une tranche on reparse l'index en entier
+        let bound_precedence = self.get_operator_precedence(&Operator::Range) + 1;
+        let checkpoint = self.current;
+        let start = if self.check(&slice_separators) {
+            None
+        } else {
+            match self.parse_expression(bound_precedence) {
+                Ok(start) if self.check(&slice_separators) => Some(Box::new(start)),
+                _ => {
+                    self.current = checkpoint;
+                    let index = self.parse_expression(0)?;
+                    self.consume(TokenType::DELIMITER(Delimiters::RSBRACKET))?;
+                    // tableau, dictionnaire ou type utilisateur : decide selon le type du receveur
+                    return Ok(Expression::CollectionAccess(CollectionAccess::Unknown(Box::new(receiver), Box::new(index))));
+                }
+            }
+        };
+
+        // `a[::2]` : le lexer produit '::' d'un seul tenant
+        let (end, step, inclusive) = if self.match_token(&[TokenType::DELIMITER(Delimiters::DOUBLECOLON)]) {
+            let step = if self.check(&[TokenType::DELIMITER(Delimiters::RSBRACKET)]) {
+                None
+            } else {
+                Some(Box::new(self.parse_expression(0)?))
+            };
+            (None, step, false)
+        } else if self.match_token(&[TokenType::DELIMITER(Delimiters::COLON)]) {
+            let end = if self.check(&[TokenType::DELIMITER(Delimiters::COLON), TokenType::DELIMITER(Delimiters::RSBRACKET)]) {
+                None
+            } else {
+                Some(Box::new(self.parse_expression(0)?))
+            };
+            let step = if self.match_token(&[TokenType::DELIMITER(Delimiters::COLON)]) && !self.check(&[TokenType::DELIMITER(Delimiters::RSBRACKET)]) {
+                Some(Box::new(self.parse_expression(0)?))
+            } else {
+                None
+            };
+            (end, step, false)
+        } else {
+            let inclusive = self.match_token(&[TokenType::OPERATOR(Operators::DOTDOTEQUAL)]);
+            if !inclusive {
+                self.consume(TokenType::OPERATOR(Operators::DOTDOT))?;
+            }
+            let end = if self.check(&[TokenType::DELIMITER(Delimiters::RSBRACKET)]) {
+                None
+            } else {
+                Some(Box::new(self.parse_expression(bound_precedence)?))
+            };
+            // '..=' sans fin, ou melange avec la forme ':'
+            if (inclusive && end.is_none()) || !self.check(&[TokenType::DELIMITER(Delimiters::RSBRACKET)]) {
+                return Err(ParserError::new(InvalidSlice, self.current_position()));
+            }
+            (end, None, inclusive)
+        };
+        self.consume(TokenType::DELIMITER(Delimiters::RSBRACKET))?;
+
+        Ok(Expression::ArraySlice(ArraySlice {
+            array: Box::new(receiver),
+            start,
+            end,
+            step,
+            inclusive,
+        }))
+    }
+
     pub fn parse_postfix_expression(&mut self) -> Result<Expression, ParserError> {
         let mut expr = self.parse_primary_expression()?;
 
@@ -219,88 +336,41 @@ impl Parser {
             expr = match &token.token_type {
                 TokenType::DELIMITER(Delimiters::LSBRACKET) => {
                     self.advance(); // Consume [
-
-                    // Parse start
-                    let start = if !self.check(&[TokenType::DELIMITER(Delimiters::COLON)]) {
-                        Some(Box::new(self.parse_expression(0)?))
-                    } else {
-                        None
-                    };
-
-                    // Si on trouve .. ou :, c'est un slice
-                    if self.check(&[TokenType::OPERATOR(Operators::DOTDOT)]) ||
-                        self.check(&[TokenType::DELIMITER(Delimiters::COLON)]) {
-                        self.advance(); // Consomme '..' ou ':'
-
-                        // Parse end
-                        let end = if !self.check(&[TokenType::DELIMITER(Delimiters::COLON)]) &&
-                            !self.check(&[TokenType::DELIMITER(Delimiters::RSBRACKET)]) {
-                            Some(Box::new(self.parse_expression(0)?))
-                        } else {
-                            None
-                        };
-
-                        // Parse step
-                        let step = if self.check(&[TokenType::DELIMITER(Delimiters::COLON)]) {
-                            self.advance(); // Consomme ':'
-                            Some(Box::new(self.parse_expression(0)?))
-                        } else {
-                            None
-                        };
-
-                        self.consume(TokenType::DELIMITER(Delimiters::RSBRACKET))?;
-
-                        // Ne pas créer de RangeExpression, traiter start comme une valeur normale
-                        Expression::ArraySlice(ArraySlice {
-                            array: Box::new(expr),
-                            start,
-                            end,
-                            step
-                        })
-                    } else if let Some(start) = start {
-                        // Simple index access
-                        self.consume(TokenType::DELIMITER(Delimiters::RSBRACKET))?;
-                        match &*start {
-                            Expression::Literal(Literal::String(_)) => {
-                                Expression::DictAccess(DictAccess {
-                                    dict: Box::new(expr),
-                                    key: start
-                                })
-                            },
-                            _ => Expression::IndexAccess(IndexAccess {
-                                array: Box::new(expr),
-                                index: start
-                            })
-                        }
-                    } else {
-                        return Err(ParserError::new(UnexpectedToken, self.current_position()));
-                    }
+                    self.parse_index_or_slice(expr)?
                 },
 
                 TokenType::DELIMITER(Delimiters::LPAR) => {
                     self.advance();
-                    let arguments = self.parse_arguments_list()?;
+                    let (arguments, keyword_arguments) = self.parse_arguments_list()?;
                     // self.expect_token(&TokenType::DELIMITER(Delimiters::RPAR))?;
                     self.consume(TokenType::DELIMITER(Delimiters::RPAR))?;
                     Expression::FunctionCall(FunctionCall {
                         name: Box::new(expr),
-                        arguments
+                        arguments,
+                        keyword_arguments,
                     })
                 },
                 TokenType::DELIMITER(Delimiters::DOT) => {
                     self.advance();
-                    if let Some(TokenType::IDENTIFIER { name }) = self.current_token().map(|t| &t.token_type) {
+                    if let Some(index) = self.tuple_indices()? {
+                        // `t.0.1` arrive en un seul FLOAT `0.1` : un acces par indice
+                        index.into_iter().fold(expr, |tuple, index| Expression::TupleAccess(TupleAccess {
+                            tuple: Box::new(tuple),
+                            index,
+                        }))
+                    } else if let Some(TokenType::IDENTIFIER { name }) = self.current_token().map(|t| &t.token_type) {
                         let name = name.clone();
                         self.advance();
                         if self.check(&[TokenType::DELIMITER(Delimiters::LPAR)]) {
                             self.advance();
-                            let arguments = self.parse_arguments_list()?;
+                            let (arguments, keyword_arguments) = self.parse_arguments_list()?;
                             // self.expect_token(&TokenType::DELIMITER(Delimiters::RPAR))?;
                             self.consume(TokenType::DELIMITER(Delimiters::RPAR))?;
                             Expression::MethodCall(MethodCall {
                                 object: Box::new(expr),
                                 method: name,
-                                arguments
+                                arguments,
+                                keyword_arguments,
                             })
                         } else {
                             Expression::MemberAccess(MemberAccess {
@@ -312,6 +382,13 @@ impl Parser {
                         return Err(ParserError::new(UnexpectedToken, self.current_position()));
                     }
                 },
+                // Propagation d'erreur: expr?
+                TokenType::OPERATOR(Operators::INTERROGATION) => {
+                    self.advance();
+                    Expression::Try(TryExpression {
+                        expression: Box::new(expr)
+                    })
+                },
                 _ => break,
             };
         }
@@ -320,6 +397,22 @@ impl Parser {
     }
 
 
+    /// indices apres le `.` d'un acces a un tuple (`t.0` ou `t.0.1`), None si ce n'en est pas un
+    fn tuple_indices(&mut self) -> Result<Option<Vec<usize>>, ParserError> {
+        let Some(token) = self.current_token() else { return Ok(None) };
+        if !matches!(token.token_type, TokenType::INTEGER { .. } | TokenType::FLOAT { .. }) {
+            return Ok(None);
+        }
+        let indices = token
+            .text
+            .split('.')
+            .map(|index| index.parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ParserError::new(UnexpectedToken, self.current_position()))?;
+        self.advance();
+        Ok(Some(indices))
+    }
+
     pub fn parse_destructuring_assignment(&mut self) -> Result<Expression,ParserError>{
         println!("Début du parsing de l'assignation destructuree[");
         self.consume(TokenType::DELIMITER(Delimiters::LSBRACKET))?;
@@ -353,6 +446,171 @@ impl Parser {
         }
     }
 
+    /// fonction pour parser x if condition else y
+    /// sans 'else' apres la condition, ce n'est pas une expression conditionnelle:
+    /// on revient en arriere et l'expression est rendue telle quelle
+    pub fn parse_conditional_expression(&mut self, then_branch: Expression) -> Result<Expression, ParserError> {
+        let start = self.current;
+        self.consume(TokenType::KEYWORD(Keywords::IF))?;
+
+        match self.parse_expression(0) {
+            Ok(condition) if self.match_token(&[TokenType::KEYWORD(Keywords::ELSE)]) => {
+                let else_branch = self.parse_expression(0)?;
+                Ok(Expression::Conditional(Conditional {
+                    condition: Box::new(condition),
+                    then_block: Box::new(then_branch),
+                    else_block: Box::new(else_branch),
+                }))
+            }
+            _ => {
+                self.current = start;
+                Ok(then_branch)
+            }
+        }
+    }
+
+    /// fonction pour parser if c { a } else { b } utilise comme expression
+    pub fn parse_if_expression(&mut self) -> Result<Expression, ParserError> {
+        println!("Début du parsing de l'expression if");
+        self.consume(TokenType::KEYWORD(Keywords::IF))?;
+        let condition = if self.check(&[TokenType::KEYWORD(Keywords::LET)]) {
+            self.parse_if_condition()?
+        } else {
+            self.parse_expression_without_struct_literal()?
+        };
+        let then_block = BlockExpression {
+            statements: self.parse_block_expression()?,
+        };
+
+        if !self.match_token(&[TokenType::KEYWORD(Keywords::ELSE)]) {
+            return Err(ParserError::new(MissingElseBranch, self.current_position()));
+        }
+        let else_block = if self.check(&[TokenType::KEYWORD(Keywords::IF)]) {
+            self.parse_if_expression()?
+        } else {
+            Expression::Block(BlockExpression {
+                statements: self.parse_block_expression()?,
+            })
+        };
+
+        println!("Fin du parsing de l'expression if OK!!!!!!!!!!!!!!");
+        Ok(Expression::Conditional(Conditional {
+            condition: Box::new(condition),
+            then_block: Box::new(Expression::Block(then_block)),
+            else_block: Box::new(else_block),
+        }))
+    }
+
+    /// fonction pour parser les casts: expr as Type
+    /// 'as' est plus prioritaire que les operateurs binaires mais moins que les operateurs unaires
+    /// -x as float  => (-x) as float
+    pub fn parse_cast_expression(&mut self) -> Result<Expression, ParserError> {
+        let mut expr = self.parse_unary_expression()?;
+
+        while self.allow_as_cast && self.match_token(&[TokenType::KEYWORD(Keywords::AS)]) {
+            let target_type = self.parse_type()?;
+            expr = Expression::TypeCast(TypeCast {
+                expression: Box::new(expr),
+                target_type,
+            });
+        }
+        Ok(expr)
+    }
+
+    /// parse une expression ou 'as' n'est pas un cast mais un alias (with ... as x, except E as e)
+    pub fn parse_expression_without_cast(&mut self) -> Result<Expression, ParserError> {
+        let allow_as_cast = self.allow_as_cast;
+        self.allow_as_cast = false;
+        let result = self.parse_expression(0);
+        self.allow_as_cast = allow_as_cast;
+        result
+    }
+
+    /// parse une expression dont le `{` suivant ouvre un bloc : `x {` n'y est pas un litteral
+    /// de structure (il faut ecrire `if p == (Point { x: 0, y: 0 }) { ... }`)
+    pub fn parse_expression_without_struct_literal(&mut self) -> Result<Expression, ParserError> {
+        let allow_struct_literal = self.allow_struct_literal;
+        self.allow_struct_literal = false;
+        let result = self.parse_expression(0);
+        self.allow_struct_literal = allow_struct_literal;
+        result
+    }
+
+    /// parse la condition d'un if/while/for/match, suivie d'un bloc en mode accolades
+    pub fn parse_condition_expression(&mut self) -> Result<Expression, ParserError> {
+        match self.syntax_mode {
+            SyntaxMode::Braces => self.parse_expression_without_struct_literal(),
+            SyntaxMode::Indentation => self.parse_expression(0),
+        }
+    }
+
+    /// parse la condition d'un if/elif/while : une expression ou `let motif = valeur`
+    pub fn parse_if_condition(&mut self) -> Result<Expression, ParserError> {
+        if !self.match_token(&[TokenType::KEYWORD(Keywords::LET)]) {
+            return self.parse_condition_expression();
+        }
+        let pattern = self.parse_pattern()?;
+        self.consume(TokenType::OPERATOR(Operators::EQUAL))?;
+        let value = self.parse_condition_expression()?;
+        Ok(Expression::LetCondition(LetCondition {
+            pattern,
+            value: Box::new(value),
+        }))
+    }
+
+    /// `Nom {` commence-t-il un litteral de structure ? `{` doit etre suivi de `}`, `..`,
+    /// ou d'un champ (`x:`, `x,`, `x }`)
+    fn at_struct_literal(&self) -> bool {
+        if !self.allow_struct_literal || !self.check(&[TokenType::DELIMITER(Delimiters::LCURBRACE)]) {
+            return false;
+        }
+        let token = |offset: usize| self.tokens.get(self.current + offset).map(|token| &token.token_type);
+        match token(1) {
+            Some(TokenType::DELIMITER(Delimiters::RCURBRACE)) | Some(TokenType::OPERATOR(Operators::DOTDOT)) => true,
+            Some(TokenType::IDENTIFIER { .. }) => matches!(
+                token(2),
+                Some(TokenType::DELIMITER(Delimiters::COLON | Delimiters::COMMA | Delimiters::RCURBRACE))
+            ),
+            _ => false,
+        }
+    }
+
+    /// `Point { x: 1, y }` ou `Point { x: 0, ..base }` ; `y` seul equivaut a `y: y`
+    fn parse_struct_literal(&mut self, name: String) -> Result<Expression, ParserError> {
+        println!("Début du parsing du litteral de structure {}", name);
+        self.consume(TokenType::DELIMITER(Delimiters::LCURBRACE))?;
+        let allow_struct_literal = self.allow_struct_literal;
+        self.allow_struct_literal = true;
+
+        let mut fields = Vec::new();
+        let mut base = None;
+        while !self.check(&[TokenType::DELIMITER(Delimiters::RCURBRACE)]) {
+            if self.match_token(&[TokenType::OPERATOR(Operators::DOTDOT)]) {
+                // la base de mise a jour termine le litteral
+                base = Some(Box::new(self.parse_expression(0)?));
+                break;
+            }
+            let field = self.consume_identifier()?;
+            let value = if self.match_token(&[TokenType::DELIMITER(Delimiters::COLON)]) {
+                self.parse_expression(0)?
+            } else {
+                Expression::Identifier(field.clone())
+            };
+            fields.push(FieldInit { name: field, value });
+
+            if !self.match_token(&[TokenType::DELIMITER(Delimiters::COMMA)]) {
+                break;
+            }
+        }
+        self.allow_struct_literal = allow_struct_literal;
+        if !self.match_token(&[TokenType::DELIMITER(Delimiters::RCURBRACE)]) {
+            return Err(ParserError::new(ExpectedCommaOrCloseBrace, self.current_position()));
+        }
+
+        println!("Fin du parsing du litteral de structure OK!!!!!!!!!!!!!!");
+        Ok(Expression::StructLiteral(StructLiteral { name, fields, base }))
+    }
+
     pub fn parse_unary_expression(&mut self) -> Result<Expression, ParserError> {
         println!("Début du parsing de l'expression unaire");
         println!("Début du parsing de l'expression unaire, current_token = {:?}", self.current_token());
@@ -361,12 +619,21 @@ impl Parser {
                 //Gestion de la Negation (-)
                 TokenType::OPERATOR(Operators::MINUS) => {
                     self.advance();
-                    let right = self.parse_unary_expression()?;
+                    let right = self.parse_unary_operand()?;
                     return Ok(Expression::UnaryOperation(UnaryOperation{
                         operator: UnaryOperator::Negative,
                         operand: Box::new(right),
                     }));
                 }
+                // Complement binaire (~)
+                TokenType::OPERATOR(Operators::TILDE) => {
+                    self.advance();
+                    let right = self.parse_unary_operand()?;
+                    Ok(Expression::UnaryOperation(UnaryOperation{
+                        operator: UnaryOperator::BitwiseNot,
+                        operand: Box::new(right),
+                    }))
+                }
                 // Gestion de la Negation  Logique (!)
                 TokenType::OPERATOR(Operators::EXCLAMATION) => {
                     self.advance();
@@ -376,6 +643,23 @@ impl Parser {
                         operand: Box::new(right),
                     }));
                 }
+                // Negation logique en toutes lettres: not a == b  => not (a == b)
+                TokenType::KEYWORD(Keywords::NOT) => {
+                    self.advance();
+                    let right = self.parse_expression(3)?;
+                    Ok(Expression::UnaryOperation(UnaryOperation{
+                        operator: UnaryOperator::Not,
+                        operand: Box::new(right),
+                    }))
+                }
+                // await expr : porte sur toute l'expression postfixe, await client.get(url).json()
+                TokenType::KEYWORD(Keywords::AWAIT) => {
+                    self.advance();
+                    let right = self.parse_unary_expression()?;
+                    Ok(Expression::Await(AwaitExpression{
+                        expression: Box::new(right),
+                    }))
+                }
                 // Gestion de la Reference(Borrowing) (&)
                 TokenType::OPERATOR(Operators::AMPER) => {
                     self.advance();
@@ -401,6 +685,55 @@ impl Parser {
     }
 
 
+    /// Suite d'une comparaison : a < b <= c devient une chaine plutot que (a < b) <= c
+    fn parse_comparison_chain(&mut self, left: Expression, operator: Operator, right: Expression) -> Result<Expression, ParserError> {
+        let mut operands = vec![left, right];
+        let mut operators = vec![operator];
+
+        while let Some(operator) = self.peek_operator() {
+            if !self.is_comparison_operator(&operator) {
+                break;
+            }
+            self.advance();
+            if let Operator::NotIn | Operator::IsNot = operator {
+                self.advance();
+            }
+            let operand_precedence = self.get_operator_precedence(&operator) + 1;
+            operands.push(self.parse_expression(operand_precedence)?);
+            operators.push(operator);
+        }
+
+        if operators.len() == 1 {
+            let right = operands.pop().unwrap();
+            let left = operands.pop().unwrap();
+            return Ok(Expression::BinaryOperation(BinaryOperation{
+                left: Box::new(left),
+                operator: operators.pop().unwrap(),
+                right: Box::new(right),
+            }));
+        }
+
+        Ok(Expression::ChainedComparison(ChainedComparison{
+            operands,
+            operators,
+        }))
+    }
+
+    /// Operande de '-' et '~' : '**' lie plus fort que l'unaire, -2 ** 2 => -(2 ** 2)
+    fn parse_unary_operand(&mut self) -> Result<Expression, ParserError> {
+        let operand = self.parse_unary_expression()?;
+        if self.match_token(&[TokenType::OPERATOR(Operators::DOUBLESTAR)]) {
+            let exponent = self.parse_expression(self.get_operator_precedence(&Operator::Power))?;
+            return Ok(Expression::BinaryOperation(BinaryOperation{
+                left: Box::new(operand),
+                operator: Operator::Power,
+                right: Box::new(exponent),
+            }));
+        }
+        Ok(operand)
+    }
+
+
     pub fn parse_primary_expression(&mut self) -> Result<Expression, ParserError> {
         println!("Début du parsing de l'expression primaire, current_token = {:?}", self.current_token());
         if let Some(token) = self.current_token() {
@@ -455,16 +788,29 @@ impl Parser {
                 TokenType::IDENTIFIER { name } => {
                     let name = name.clone();
                     self.advance();
-                    Expression::Identifier(name)
+                    if self.at_struct_literal() {
+                        self.parse_struct_literal(name)?
+                    } else {
+                        Expression::Identifier(name)
+                    }
                 }
                 TokenType::KEYWORD(Keywords::LAMBDA) => {
                     // self.advance();
                     self.parse_lambda_expression()?
                 }
+                TokenType::KEYWORD(Keywords::IF) => self.parse_if_expression()?,
+                TokenType::KEYWORD(Keywords::MOVE)
+                | TokenType::OPERATOR(Operators::VBAR)
+                | TokenType::OPERATOR(Operators::OR) => self.parse_closure_expression()?,
 
                 TokenType::DELIMITER(Delimiters::LPAR) => {
                     self.advance();
-                    let expr = self.parse_expression(0)?;
+                    // entre parentheses, un litteral de structure redevient possible
+                    let allow_struct_literal = self.allow_struct_literal;
+                    self.allow_struct_literal = true;
+                    let expr = self.parse_parenthesized_or_tuple();
+                    self.allow_struct_literal = allow_struct_literal;
+                    let expr = expr?;
                     if let Some(token) = self.current_token() {
                         if matches!(token.token_type, TokenType::DELIMITER(Delimiters::RPAR)) {
                             self.advance();
@@ -495,6 +841,27 @@ impl Parser {
     }
 
 
+    /// contenu de `( ... )` apres la parenthese ouvrante : `(x)` est un regroupement,
+    /// `()`, `(x,)` et `(x, y)` sont des tuples ; la parenthese fermante reste a consommer
+    fn parse_parenthesized_or_tuple(&mut self) -> Result<Expression, ParserError> {
+        if self.check(&[TokenType::DELIMITER(Delimiters::RPAR)]) {
+            return Ok(Expression::Tuple(TupleExpression { elements: Vec::new() }));
+        }
+        let first = self.parse_expression(0)?;
+        if !self.match_token(&[TokenType::DELIMITER(Delimiters::COMMA)]) {
+            return Ok(first);
+        }
+        let mut elements = vec![first];
+        while !self.check(&[TokenType::DELIMITER(Delimiters::RPAR)]) {
+            elements.push(self.parse_expression(0)?);
+            if !self.match_token(&[TokenType::DELIMITER(Delimiters::COMMA)]) {
+                break;
+            }
+        }
+        println!("Tuple parsé : {} elements", elements.len());
+        Ok(Expression::Tuple(TupleExpression { elements }))
+    }
+
     pub fn parse_lambda_expression(&mut self) -> Result<Expression, ParserError> {
         println!("Début du parsing de l'expression lambda");
         self.consume(TokenType::KEYWORD(Keywords::LAMBDA))?;
@@ -527,29 +894,112 @@ impl Parser {
             parameters,
             return_type: Some(return_type),
             body,
+            is_move: false,
+            captures: Vec::new(),
         }))
 
     }
 
+    /// fonction pour parser les fermetures a la Rust : `|x| x + 1`, `|x: int, y| -> int { ... }`,
+    /// `|| 42` et `move |x| x + n` ; le type d'un parametre non annote est infere
+    pub fn parse_closure_expression(&mut self) -> Result<Expression, ParserError> {
+        println!("Début du parsing de la fermeture");
+        let is_move = self.match_token(&[TokenType::KEYWORD(Keywords::MOVE)]);
+
+        let mut parameters = Vec::new();
+        // `||` est lu comme un seul operateur : fermeture sans parametre
+        if !self.match_token(&[TokenType::OPERATOR(Operators::OR)]) {
+            self.consume(TokenType::OPERATOR(Operators::VBAR))?;
+            while !self.check(&[TokenType::OPERATOR(Operators::VBAR)]) {
+                let name = self.consume_identifier()?;
+                let parameter_type = if self.match_token(&[TokenType::DELIMITER(Delimiters::COLON)]) {
+                    self.parse_type()?
+                } else {
+                    Type::Infer
+                };
+                parameters.push(Parameter { name, parameter_type, default_value: None, kind: ParameterKind::Regular });
+                if !self.match_token(&[TokenType::DELIMITER(Delimiters::COMMA)]) {
+                    break;
+                }
+            }
+            self.consume(TokenType::OPERATOR(Operators::VBAR))?;
+        }
+
+        // en mode indentation, `:` ouvre un bloc indente
+        let indented_block = |parser: &Self| {
+            parser.syntax_mode == SyntaxMode::Indentation && parser.check(&[TokenType::DELIMITER(Delimiters::COLON)])
+        };
+
+        // avec un type de retour, le corps est forcement un bloc
+        let return_type = if self.match_token(&[TokenType::OPERATOR(Operators::RARROW)]) {
+            let return_type = self.parse_type()?;
+            if !self.check(&[TokenType::DELIMITER(Delimiters::LCURBRACE)]) && !indented_block(self) {
+                return Err(ParserError::new(ExpectedArrowOrBlock, self.current_position()));
+            }
+            return_type
+        } else {
+            Type::Infer
+        };
+
+        let body = if indented_block(self) {
+            self.parse_block()?
+        } else if self.check(&[TokenType::DELIMITER(Delimiters::LCURBRACE)]) {
+            self.parse_block_expression()?
+        } else {
+            vec![ASTNode::Expression(self.parse_expression(0)?)]
+        };
+
+        println!("Fin du parsing de la fermeture OK!!!!!!!!!!!!!!");
+        Ok(Expression::LambdaExpression(LambdaExpression {
+            parameters,
+            return_type: Some(return_type),
+            body,
+            is_move,
+            captures: Vec::new(),
+        }))
+    }
+
     /// fonction pour parser les parametres
 
-    pub fn parse_arguments_list(&mut self) -> Result<Vec<Expression>, ParserError> {
+    /// arguments d'un appel : positionnels puis nommes (`f(a, b, port=80)`)
+    pub fn parse_arguments_list(&mut self) -> Result<(Vec<Expression>, Vec<KeywordArgument>), ParserError> {
         println!("Début du parsing de la liste d'arguments");
         let mut arguments = Vec::new();
+        let mut keyword_arguments = Vec::new();
         if self.check(&[TokenType::DELIMITER(Delimiters::RPAR)]){
-            return Ok(arguments);
+            return Ok((arguments, keyword_arguments));
         }
         loop {
-            let argument = self.parse_expression(0);
-            arguments.push(argument?);
+            if self.at_keyword_argument() {
+                let name = self.consume_identifier()?;
+                self.consume(TokenType::OPERATOR(Operators::EQUAL))?;
+                let value = self.parse_expression(0)?;
+                keyword_arguments.push(KeywordArgument { name, value });
+            } else if !keyword_arguments.is_empty() {
+                return Err(ParserError::new(PositionalArgumentAfterKeyword, self.current_position()));
+            } else {
+                let argument = self.parse_expression(0);
+                arguments.push(argument?);
+            }
 
             if !self.match_token(&[TokenType::DELIMITER(Delimiters::COMMA)]) {
                 break;
             }
+            // virgule finale autorisee : f(a, b,)
+            if self.check(&[TokenType::DELIMITER(Delimiters::RPAR)]) {
+                break;
+            }
         }
-        println!("Arguments liste parsés : {:?}", arguments);
-        Ok(arguments)
+        println!("Arguments liste parsés : {:?} {:?}", arguments, keyword_arguments);
+        Ok((arguments, keyword_arguments))
+
+    }
 
+    /// `nom=` en debut d'argument introduit un argument nomme
+    fn at_keyword_argument(&self) -> bool {
+        let token = |offset: usize| self.tokens.get(self.current + offset).map(|token| &token.token_type);
+        matches!(token(0), Some(TokenType::IDENTIFIER { .. }))
+            && matches!(token(1), Some(TokenType::OPERATOR(Operators::EQUAL)))
     }
 
     pub fn parse_parameter_list(&mut self) -> Result<Vec<Parameter>, ParserError> {
@@ -574,6 +1024,8 @@ impl Parser {
             parameters.push(Parameter {
                 name: param_name,
                 parameter_type: param_type.unwrap_or(Type::Infer),
+                default_value: None,
+                kind: ParameterKind::Regular,
             });
 
             // Si le prochain token est une virgule, continuer
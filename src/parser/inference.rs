@@ -1,5 +1,14 @@
 use std::collections::HashMap;
-use crate::parser::ast::{Assignment, BinaryOperation, Expression, Literal, Operator, Type, UnaryOperation, UnaryOperator, VariableDeclaration};
+use crate::parser::ast::{ArrayExpression, ArraySlice, Assignment, ASTNode, AwaitExpression, BinaryOperation, BlockExpression, ChainedComparison, CollectionAccess, Conditional, Declaration, DestructuringDeclaration, DictAccess, Expression, FunctionCall, FunctionDeclaration, IndexAccess, LambdaExpression, LetCondition, ListComprehension, Literal, MatchArm, NamedExpression, Operator, Pattern, Statement, StructDeclaration, StructLiteral, StructPattern, TryExpression, TupleAccess, Type, TypeCast, UnaryOperation, UnaryOperator, VariableDeclaration, YieldStatement};
+use crate::semantic::arguments::{bind_arguments, local_type, Binding, ParameterSpec};
+use crate::semantic::builtins::{check_await, check_try, future_type, iterator_type, option_type, result_type, BuiltinEnum};
+use crate::semantic::generators::is_generator;
+use crate::semantic::cast::check_cast;
+use crate::semantic::indexing::{index_kind, index_type, rewrite_index, slice_type};
+use crate::modules::loader::Symbol;
+
+/// Types generiques integres dont on peut chercher ou parcourir les elements
+const CONTAINERS: [&str; 9] = ["Array", "List", "Vec", "Set", "Range", "Dict", "HashMap", "Iterator", "Generator"];
 
 
 
@@ -8,6 +17,20 @@ use crate::parser::ast::{Assignment, BinaryOperation, Expression, Literal, Opera
 pub struct TypeContext {
     type_vars: HashMap<String, Type>,
     constraints: Vec<TypeConstraint>,
+    // type de retour de la fonction englobante, pour l'operateur '?'
+    return_type: Option<Type>,
+    // variables d'iteration des comprehensions en cours, qu'un ':=' ne peut pas relier
+    comprehension_targets: Vec<String>,
+    // type d'un appel pour chaque fonction declaree (Future<T> pour une async fn)
+    functions: HashMap<String, Type>,
+    // vrai dans le corps d'une async fn, seul endroit ou 'await' est permis
+    in_async: bool,
+    // type des valeurs produites par le generateur en cours, None hors d'un generateur
+    yield_type: Option<Type>,
+    // structures declarees, pour verifier les litteraux `Point { x: 1, y: 2 }`
+    structs: HashMap<String, Vec<(String, Type)>>,
+    // parametres de chaque fonction declaree, pour lier les arguments des appels
+    signatures: HashMap<String, Vec<ParameterSpec>>,
 }
 
 #[allow(dead_code)]
@@ -19,21 +42,684 @@ pub enum TypeConstraint {
 }
 
 
+impl Default for TypeContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TypeContext {
     pub fn new() -> Self {
         TypeContext {
             type_vars: HashMap::new(),
             constraints: Vec::new(),
+            return_type: None,
+            comprehension_targets: Vec::new(),
+            functions: HashMap::new(),
+            in_async: false,
+            yield_type: None,
+            structs: HashMap::new(),
+            signatures: HashMap::new(),
+        }
+    }
+
+    /// Contexte pour le corps d'une fonction renvoyant `return_type`
+    pub fn with_return_type(mut self, return_type: Type) -> Self {
+        self.return_type = Some(return_type);
+        self
+    }
+
+    /// Enregistre la signature d'une fonction : un appel a une async fn renvoie un Future,
+    /// un appel a un generateur renvoie un Iterator
+    pub fn declare_function(&mut self, function: &FunctionDeclaration) {
+        let return_type = function.return_type.clone().unwrap_or(Type::Infer);
+        let call_type = if is_generator(&function.body) {
+            match generator_item(&return_type) {
+                Some(item) => iterator_type(item),
+                None => iterator_type(Type::Infer),
+            }
+        } else if function.is_async {
+            future_type(return_type)
+        } else {
+            return_type
+        };
+        self.functions.insert(function.name.clone(), call_type);
+        self.signatures.insert(
+            function.name.clone(),
+            function.parameters.iter().map(ParameterSpec::from).collect(),
+        );
+    }
+
+    /// Enregistre les champs d'une structure
+    pub fn declare_struct(&mut self, declaration: &StructDeclaration) {
+        let fields = declaration.fields.iter().map(|field| (field.name.clone(), field.field_type.clone())).collect();
+        self.structs.insert(declaration.name.clone(), fields);
+    }
+
+    /// Rend visibles les symboles importes d'autres modules, sous leur nom local
+    pub fn declare_imports(&mut self, symbols: &HashMap<String, Symbol>) -> Result<(), String> {
+        for (local, symbol) in symbols {
+            match &symbol.declaration {
+                Some(Declaration::Function(function)) => {
+                    let mut function = function.clone();
+                    function.name = local.clone();
+                    self.declare_function(&function);
+                }
+                Some(Declaration::Constante(constant)) => {
+                    let constant_type = match &constant.constant_type {
+                        Some(constant_type) => constant_type.clone(),
+                        None => self.infer_expression(&constant.value)?,
+                    };
+                    self.type_vars.insert(local.clone(), constant_type);
+                }
+                Some(Declaration::Structure(structure)) => {
+                    let mut structure = structure.clone();
+                    structure.name = local.clone();
+                    self.declare_struct(&structure);
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Verifie le corps d'une fonction : parametres, declarations, retours, '?', 'await' et 'yield'
+    pub fn check_function(&mut self, function: &FunctionDeclaration) -> Result<(), String> {
+        self.declare_function(function);
+        let declared_return = function.return_type.clone().unwrap_or(Type::Infer);
+
+        // un argument nomme ne pourrait designer qu'un seul des parametres homonymes
+        for (index, parameter) in function.parameters.iter().enumerate() {
+            if function.parameters[..index].iter().any(|previous| previous.name == parameter.name) {
+                return Err(format!("Duplicate parameter '{}' in function '{}'", parameter.name, function.name));
+            }
+        }
+
+        let generator = is_generator(&function.body);
+        let (return_type, yield_type) = if generator {
+            if function.is_async {
+                return Err(format!("Async generator '{}' is not supported", function.name));
+            }
+            let item = generator_item(&declared_return).ok_or_else(|| format!(
+                "Generator '{}' must return Iterator<T>, found {:?}",
+                function.name, declared_return
+            ))?;
+            (None, Some(item))
+        } else {
+            (Some(declared_return), None)
+        };
+
+        let enclosing_return = std::mem::replace(&mut self.return_type, return_type);
+        let enclosing_yield = std::mem::replace(&mut self.yield_type, yield_type);
+        let enclosing_async = std::mem::replace(&mut self.in_async, function.is_async);
+        for parameter in &function.parameters {
+            if let Some(default_value) = &parameter.default_value {
+                let default_type = self.infer_expression(default_value)?;
+                if !types_compatible(&parameter.parameter_type, &default_type) {
+                    return Err(format!(
+                        "Default value of '{}' must be {:?}, found {:?}",
+                        parameter.name, parameter.parameter_type, default_type
+                    ));
+                }
+            }
+            self.type_vars.insert(parameter.name.clone(), local_type(parameter));
+        }
+
+        let result = self.check_block(&function.body);
+        if generator && result.is_ok() {
+            // le type des valeurs produites est connu une fois le corps parcouru
+            let item = self.yield_type.clone().unwrap_or(Type::Infer);
+            self.functions.insert(function.name.clone(), iterator_type(item));
+        }
+
+        self.return_type = enclosing_return;
+        self.yield_type = enclosing_yield;
+        self.in_async = enclosing_async;
+        result
+    }
+
+    /// Remplace chaque `x[cle]` du corps par l'acces correspondant au type de `x`
+    /// (voir `semantic::indexing`) ; les acces dont le receveur reste inconnu sont laisses tels quels
+    pub fn resolve_indexing(&mut self, function: &mut FunctionDeclaration) -> Result<(), String> {
+        for parameter in &function.parameters {
+            self.type_vars.insert(parameter.name.clone(), local_type(parameter));
+        }
+        self.resolve_block(&mut function.body)
+    }
+
+    fn resolve_block(&mut self, block: &mut [ASTNode]) -> Result<(), String> {
+        block.iter_mut().try_for_each(|node| self.resolve_node(node))
+    }
+
+    fn resolve_node(&mut self, node: &mut ASTNode) -> Result<(), String> {
+        match node {
+            ASTNode::Expression(expr) | ASTNode::Statement(Statement::Expression(expr)) => {
+                self.resolve_expression(expr)?;
+            }
+            ASTNode::Declaration(Declaration::Variable(decl))
+            | ASTNode::Statement(Statement::DeclarationStatement(Declaration::Variable(decl))) => {
+                if let Some(value) = &mut decl.value {
+                    self.resolve_expression(value)?;
+                }
+            }
+            ASTNode::Declaration(Declaration::Destructuring(decl))
+            | ASTNode::Statement(Statement::DeclarationStatement(Declaration::Destructuring(decl))) => {
+                self.resolve_expression(&mut decl.value)?;
+            }
+            ASTNode::Statement(Statement::Assignment(target, value)) => {
+                self.resolve_expression(target)?;
+                self.resolve_expression(value)?;
+            }
+            ASTNode::Statement(Statement::ReturnStatement(ret)) => {
+                if let Some(value) = &mut ret.value {
+                    self.resolve_expression(value)?;
+                }
+                return Ok(());
+            }
+            ASTNode::Statement(Statement::YieldStatement(yield_statement)) => {
+                if let Some(value) = &mut yield_statement.value {
+                    self.resolve_expression(value)?;
+                }
+                return Ok(());
+            }
+            ASTNode::Statement(Statement::IfStatement(if_statement)) => {
+                self.resolve_expression(&mut if_statement.condition)?;
+                self.resolve_block(&mut if_statement.then_block)?;
+                for elif in &mut if_statement.elif_block {
+                    self.resolve_expression(&mut elif.condition)?;
+                    self.resolve_block(&mut elif.block)?;
+                }
+                if let Some(else_block) = &mut if_statement.else_block {
+                    self.resolve_block(else_block)?;
+                }
+                return Ok(());
+            }
+            ASTNode::Statement(Statement::WhileStatement(while_statement)) => {
+                self.resolve_expression(&mut while_statement.condition)?;
+                return self.resolve_block(&mut while_statement.body);
+            }
+            ASTNode::Statement(Statement::ForStatement(for_statement)) => {
+                self.resolve_expression(&mut for_statement.iterable)?;
+                let iterable_type = self.infer_expression(&for_statement.iterable)?;
+                let element_type = self.contained_type(&iterable_type)?;
+                self.type_vars.insert(for_statement.iterator.clone(), element_type);
+                return self.resolve_block(&mut for_statement.body);
+            }
+            ASTNode::Statement(Statement::LoopStatement(loop_statement)) => {
+                return self.resolve_block(&mut loop_statement.body);
+            }
+            ASTNode::Statement(Statement::WithStatement(with_statement)) => {
+                return self.resolve_block(&mut with_statement.body);
+            }
+            ASTNode::Statement(Statement::TryStatement(try_statement)) => {
+                self.resolve_block(&mut try_statement.body)?;
+                for handler in &mut try_statement.handlers {
+                    self.resolve_block(&mut handler.body)?;
+                }
+                if let Some(finally_body) = &mut try_statement.finally_body {
+                    self.resolve_block(finally_body)?;
+                }
+                return Ok(());
+            }
+            ASTNode::Statement(Statement::MatchStatement(match_statement)) => {
+                return self.resolve_match(&mut match_statement.expression, &mut match_statement.arms);
+            }
+            _ => return Ok(()),
+        }
+        // declare les variables de l'instruction pour les suivantes
+        self.check_node(node)
+    }
+
+    fn resolve_expression(&mut self, expr: &mut Expression) -> Result<(), String> {
+        let rewritten = match expr {
+            Expression::CollectionAccess(
+                CollectionAccess::Array(receiver, key)
+                | CollectionAccess::Dict(receiver, key)
+                | CollectionAccess::Unknown(receiver, key),
+            )
+            | Expression::IndexAccess(IndexAccess { array: receiver, index: key })
+            | Expression::DictAccess(DictAccess { dict: receiver, key }) => {
+                self.resolve_expression(receiver)?;
+                self.resolve_expression(key)?;
+                let receiver_type = self.infer_expression(receiver)?;
+                let key_type = self.infer_expression(key)?;
+                let kind = index_kind(&receiver_type, &key_type)?;
+                rewrite_index(&kind, (**receiver).clone(), (**key).clone())
+            }
+            Expression::BinaryOperation(binop) => {
+                self.resolve_expression(&mut binop.left)?;
+                self.resolve_expression(&mut binop.right)?;
+                None
+            }
+            Expression::ChainedComparison(chain) => {
+                chain.operands.iter_mut().try_for_each(|operand| self.resolve_expression(operand))?;
+                None
+            }
+            Expression::UnaryOperation(unop) => {
+                self.resolve_expression(&mut unop.operand)?;
+                None
+            }
+            Expression::FunctionCall(call) => {
+                self.resolve_expression(&mut call.name)?;
+                call.arguments.iter_mut().try_for_each(|argument| self.resolve_expression(argument))?;
+                call.keyword_arguments.iter_mut().try_for_each(|keyword| self.resolve_expression(&mut keyword.value))?;
+                None
+            }
+            Expression::MethodCall(call) => {
+                self.resolve_expression(&mut call.object)?;
+                call.arguments.iter_mut().try_for_each(|argument| self.resolve_expression(argument))?;
+                call.keyword_arguments.iter_mut().try_for_each(|keyword| self.resolve_expression(&mut keyword.value))?;
+                None
+            }
+            Expression::MemberAccess(access) => {
+                self.resolve_expression(&mut access.object)?;
+                None
+            }
+            Expression::ArraySlice(slice) => {
+                self.resolve_expression(&mut slice.array)?;
+                for bound in [&mut slice.start, &mut slice.end, &mut slice.step].into_iter().flatten() {
+                    self.resolve_expression(bound)?;
+                }
+                None
+            }
+            Expression::Array(array) => {
+                array.elements.iter_mut().try_for_each(|element| self.resolve_expression(element))?;
+                None
+            }
+            Expression::Tuple(tuple) => {
+                tuple.elements.iter_mut().try_for_each(|element| self.resolve_expression(element))?;
+                None
+            }
+            Expression::TupleAccess(access) => {
+                self.resolve_expression(&mut access.tuple)?;
+                None
+            }
+            Expression::StructLiteral(literal) => {
+                literal.fields.iter_mut().try_for_each(|field| self.resolve_expression(&mut field.value))?;
+                if let Some(base) = &mut literal.base {
+                    self.resolve_expression(base)?;
+                }
+                None
+            }
+            Expression::Conditional(conditional) => {
+                self.resolve_expression(&mut conditional.condition)?;
+                self.resolve_expression(&mut conditional.then_block)?;
+                self.resolve_expression(&mut conditional.else_block)?;
+                None
+            }
+            Expression::Assignment(assign) => {
+                self.resolve_expression(&mut assign.target)?;
+                self.resolve_expression(&mut assign.value)?;
+                None
+            }
+            Expression::CompoundAssignment(assign) => {
+                self.resolve_expression(&mut assign.target)?;
+                self.resolve_expression(&mut assign.value)?;
+                None
+            }
+            Expression::NamedExpression(named) => {
+                self.resolve_expression(&mut named.value)?;
+                None
+            }
+            Expression::LetCondition(condition) => {
+                self.resolve_expression(&mut condition.value)?;
+                self.infer_let_condition(condition)?;
+                None
+            }
+            Expression::TypeCast(cast) => {
+                self.resolve_expression(&mut cast.expression)?;
+                None
+            }
+            Expression::Try(try_expr) => {
+                self.resolve_expression(&mut try_expr.expression)?;
+                None
+            }
+            Expression::Await(await_expr) => {
+                self.resolve_expression(&mut await_expr.expression)?;
+                None
+            }
+            // les variables locales d'un bloc, d'une fermeture ou d'une comprehension ne lui survivent pas
+            Expression::Block(block) => {
+                let enclosing_vars = self.type_vars.clone();
+                let result = self.resolve_block(&mut block.statements);
+                self.type_vars = enclosing_vars;
+                result?;
+                None
+            }
+            Expression::LambdaExpression(lambda) => {
+                let enclosing_vars = self.type_vars.clone();
+                for parameter in &lambda.parameters {
+                    self.type_vars.insert(parameter.name.clone(), parameter.parameter_type.clone());
+                }
+                let result = self.resolve_block(&mut lambda.body);
+                self.type_vars = enclosing_vars;
+                result?;
+                None
+            }
+            Expression::ListComprehension(comprehension) => {
+                let enclosing_vars = self.type_vars.clone();
+                let result = self.resolve_comprehension(comprehension);
+                self.type_vars = enclosing_vars;
+                result?;
+                None
+            }
+            Expression::MatchExpression(match_expression) => {
+                self.resolve_match(&mut match_expression.expression, &mut match_expression.arms)?;
+                None
+            }
+            _ => None,
+        };
+        if let Some(rewritten) = rewritten {
+            *expr = rewritten;
+        }
+        Ok(())
+    }
+
+    fn resolve_comprehension(&mut self, comprehension: &mut ListComprehension) -> Result<(), String> {
+        for iterator in &mut comprehension.iterators {
+            self.resolve_expression(&mut iterator.iterator)?;
+            let iterable_type = self.infer_expression(&iterator.iterator)?;
+            let element_type = self.contained_type(&iterable_type)?;
+            self.bind_pattern(&iterator.pattern, &element_type)?;
+        }
+        comprehension.conditions.iter_mut().try_for_each(|condition| self.resolve_expression(condition))?;
+        self.resolve_expression(&mut comprehension.elements)
+    }
+
+    /// Chaque bras voit les noms lies par son motif
+    fn resolve_match(&mut self, scrutinee: &mut Expression, arms: &mut [MatchArm]) -> Result<(), String> {
+        self.resolve_expression(scrutinee)?;
+        let scrutinee_type = self.infer_expression(scrutinee)?;
+        for arm in arms {
+            let enclosing_vars = self.type_vars.clone();
+            let result = self.bind_pattern(&arm.pattern, &scrutinee_type).and_then(|()| {
+                if let Some(guard) = &mut arm.guard {
+                    self.resolve_expression(guard)?;
+                }
+                self.resolve_block(&mut arm.body)
+            });
+            self.type_vars = enclosing_vars;
+            result?;
+        }
+        Ok(())
+    }
+
+    fn check_block(&mut self, block: &[ASTNode]) -> Result<(), String> {
+        block.iter().try_for_each(|node| self.check_node(node))
+    }
+
+    fn check_node(&mut self, node: &ASTNode) -> Result<(), String> {
+        match node {
+            ASTNode::Expression(expr) | ASTNode::Statement(Statement::Expression(expr)) => {
+                self.infer_expression(expr).map(|_| ())
+            }
+            ASTNode::Statement(Statement::ReturnStatement(ret)) => {
+                let Some(value) = &ret.value else { return Ok(()) };
+                if self.yield_type.is_some() {
+                    return Err("Generator functions can only use a bare 'return'".to_string());
+                }
+                let value_type = self.infer_expression(value)?;
+                match &self.return_type {
+                    Some(expected) if !types_compatible(expected, &value_type) => Err(format!(
+                        "Mismatched return type: expected {:?}, found {:?}",
+                        expected, value_type
+                    )),
+                    _ => Ok(()),
+                }
+            }
+            ASTNode::Statement(Statement::YieldStatement(yield_statement)) => {
+                self.check_yield(yield_statement)
+            }
+            ASTNode::Declaration(Declaration::Variable(decl))
+            | ASTNode::Statement(Statement::DeclarationStatement(Declaration::Variable(decl))) => {
+                let variable_type = self.infer_variable_declaration(decl)?;
+                self.type_vars.insert(decl.name.clone(), variable_type);
+                Ok(())
+            }
+            ASTNode::Declaration(Declaration::Destructuring(decl))
+            | ASTNode::Statement(Statement::DeclarationStatement(Declaration::Destructuring(decl))) => {
+                self.check_destructuring(decl)
+            }
+            ASTNode::Declaration(Declaration::Structure(structure)) => {
+                self.declare_struct(structure);
+                Ok(())
+            }
+            ASTNode::Statement(Statement::IfStatement(if_statement)) => {
+                self.infer_expression(&if_statement.condition)?;
+                self.check_block(&if_statement.then_block)?;
+                for elif in &if_statement.elif_block {
+                    self.infer_expression(&elif.condition)?;
+                    self.check_block(&elif.block)?;
+                }
+                match &if_statement.else_block {
+                    Some(else_block) => self.check_block(else_block),
+                    None => Ok(()),
+                }
+            }
+            ASTNode::Statement(Statement::WhileStatement(while_statement)) => {
+                self.infer_expression(&while_statement.condition)?;
+                self.check_block(&while_statement.body)
+            }
+            ASTNode::Statement(Statement::ForStatement(for_statement)) => {
+                // for x in generateur() : x prend le type des valeurs produites
+                let iterable_type = self.infer_expression(&for_statement.iterable)?;
+                let element_type = self.contained_type(&iterable_type)?;
+                self.type_vars.insert(for_statement.iterator.clone(), element_type);
+                self.check_block(&for_statement.body)
+            }
+            ASTNode::Statement(Statement::LoopStatement(loop_statement)) => {
+                self.check_block(&loop_statement.body)
+            }
+            ASTNode::Statement(Statement::WithStatement(with_statement)) => {
+                self.check_block(&with_statement.body)
+            }
+            ASTNode::Statement(Statement::TryStatement(try_statement)) => {
+                self.check_block(&try_statement.body)?;
+                for handler in &try_statement.handlers {
+                    self.check_block(&handler.body)?;
+                }
+                match &try_statement.finally_body {
+                    Some(finally_body) => self.check_block(finally_body),
+                    None => Ok(()),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// `let (a, mut b) = valeur` : chaque nom du motif prend le type de l'element correspondant
+    fn check_destructuring(&mut self, decl: &DestructuringDeclaration) -> Result<(), String> {
+        let value_type = self.infer_expression(&decl.value)?;
+        let bound_type = match &decl.variable_type {
+            Some(explicit_type) if !types_compatible(explicit_type, &value_type) => {
+                return Err(format!("Type mismatch: expected {:?}, found {:?}", explicit_type, value_type));
+            }
+            Some(explicit_type) => explicit_type.clone(),
+            None => value_type,
+        };
+        self.bind_pattern(&decl.pattern, &bound_type)
+    }
+
+    /// `Point { x, y: 0, .. }` : chaque champ nomme doit exister, les autres doivent etre ignores par `..`
+    fn bind_struct_pattern(&mut self, pattern: &StructPattern, value_type: &Type) -> Result<(), String> {
+        let fields = self
+            .structs
+            .get(&pattern.name)
+            .cloned()
+            .ok_or_else(|| format!("Unknown struct '{}'", pattern.name))?;
+        if *value_type != Type::Infer && *value_type != Type::Named(pattern.name.clone()) {
+            return Err(format!("Cannot match {:?} with a '{}' pattern", value_type, pattern.name));
+        }
+        for field in &pattern.fields {
+            let Some((_, field_type)) = fields.iter().find(|(name, _)| *name == field.name) else {
+                return Err(format!("Struct '{}' has no field '{}'", pattern.name, field.name));
+            };
+            self.bind_pattern(&field.pattern, field_type)?;
+        }
+        let missing: Vec<&str> = fields
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .filter(|name| !pattern.fields.iter().any(|field| field.name == *name))
+            .collect();
+        if !pattern.has_rest && !missing.is_empty() {
+            return Err(format!("Missing fields in '{}' pattern: {}", pattern.name, missing.join(", ")));
+        }
+        Ok(())
+    }
+
+    fn bind_array_pattern(&mut self, patterns: &[Pattern], array_type: &Type, element_type: &Type) -> Result<(), String> {
+        patterns.iter().try_for_each(|pattern| match pattern {
+            // `..rest` recoit le reste du tableau
+            Pattern::Binding(_, rest) if matches!(**rest, Pattern::Rest) => self.bind_pattern(pattern, array_type),
+            _ => self.bind_pattern(pattern, element_type),
+        })
+    }
+
+    /// `let motif = valeur` d'un `if let` / `while let` : lie les noms du motif
+    fn infer_let_condition(&mut self, condition: &LetCondition) -> Result<Type, String> {
+        let value_type = self.infer_expression(&condition.value)?;
+        self.bind_pattern(&condition.pattern, &value_type)?;
+        Ok(Type::Bool)
+    }
+
+    fn bind_pattern(&mut self, pattern: &Pattern, value_type: &Type) -> Result<(), String> {
+        match (pattern, value_type) {
+            (Pattern::Identifier(name) | Pattern::MutableIdentifier(name), _) => {
+                self.type_vars.insert(name.clone(), value_type.clone());
+                Ok(())
+            }
+            (Pattern::Wildcard, _) => Ok(()),
+            (Pattern::Tuple(patterns), Type::Tuple(element_types)) => {
+                if patterns.len() != element_types.len() {
+                    return Err(format!(
+                        "Tuple pattern has {} elements, found {:?}",
+                        patterns.len(),
+                        value_type
+                    ));
+                }
+                patterns
+                    .iter()
+                    .zip(element_types)
+                    .try_for_each(|(pattern, element_type)| self.bind_pattern(pattern, element_type))
+            }
+            (Pattern::Binding(name, pattern), _) => {
+                self.type_vars.insert(name.clone(), value_type.clone());
+                self.bind_pattern(pattern, value_type)
+            }
+            (Pattern::Or(alternatives), _) => {
+                let mut expected = Vec::new();
+                pattern_names(&alternatives[0], &mut expected);
+                expected.sort();
+                for alternative in alternatives {
+                    let mut names = Vec::new();
+                    pattern_names(alternative, &mut names);
+                    names.sort();
+                    if names != expected {
+                        return Err(format!(
+                            "Every alternative of an or-pattern must bind the same names: {:?} vs {:?}",
+                            expected, names
+                        ));
+                    }
+                    self.bind_pattern(alternative, value_type)?;
+                }
+                Ok(())
+            }
+            (Pattern::Array(patterns), Type::Array(element_type)) => {
+                self.bind_array_pattern(patterns, value_type, element_type)
+            }
+            (Pattern::Array(patterns), Type::Generic(generic))
+                if matches!(generic.base.as_str(), "Array" | "List" | "Vec") && generic.type_parameters.len() == 1 =>
+            {
+                self.bind_array_pattern(patterns, value_type, &generic.type_parameters[0])
+            }
+            (Pattern::Constructor(name, patterns), _) if matches!(name.as_str(), "Some" | "Ok" | "Err") => {
+                let payload = match (name.as_str(), value_type) {
+                    (_, Type::Infer) => Type::Infer,
+                    ("Some", Type::Generic(generic)) if generic.base == BuiltinEnum::Option.name() => {
+                        generic.type_parameters[0].clone()
+                    }
+                    ("Ok", Type::Generic(generic)) if generic.base == BuiltinEnum::Result.name() => {
+                        generic.type_parameters[0].clone()
+                    }
+                    ("Err", Type::Generic(generic)) if generic.base == BuiltinEnum::Result.name() => {
+                        generic.type_parameters[1].clone()
+                    }
+                    _ => return Err(format!("Pattern '{}(..)' cannot match {:?}", name, value_type)),
+                };
+                patterns.iter().try_for_each(|pattern| self.bind_pattern(pattern, &payload))
+            }
+            (Pattern::Struct(pattern), _) => self.bind_struct_pattern(pattern, value_type),
+            (Pattern::Tuple(_), other) if *other != Type::Infer => {
+                Err(format!("Cannot destructure {:?} with a tuple pattern", other))
+            }
+            _ => {
+                let mut names = Vec::new();
+                pattern_names(pattern, &mut names);
+                for name in names {
+                    self.type_vars.insert(name, Type::Infer);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// `yield x` produit un x, `yield from xs` produit chaque element de xs
+    fn check_yield(&mut self, yield_statement: &YieldStatement) -> Result<(), String> {
+        let Some(expected) = self.yield_type.clone() else {
+            return Err("'yield' is only allowed inside a generator function".to_string());
+        };
+        let produced = match &yield_statement.value {
+            None => Type::Infer,
+            Some(value) if yield_statement.from => {
+                let iterable_type = self.infer_expression(value)?;
+                self.contained_type(&iterable_type)?
+            }
+            Some(value) => self.infer_expression(value)?,
+        };
+
+        if expected == Type::Infer {
+            self.yield_type = Some(produced);
+            Ok(())
+        } else if types_compatible(&expected, &produced) {
+            Ok(())
+        } else {
+            Err(format!("Mismatched yield type: expected {:?}, found {:?}", expected, produced))
         }
     }
 
     pub fn infer_expression(&mut self, expr: &Expression) -> Result<Type, String> {
         match expr {
             Expression::Literal(lit) => self.infer_literal(lit),
+            Expression::Identifier(name) if name == "None" => Ok(option_type(Type::Infer)),
             Expression::Identifier(name) => self.lookup_type(name),
+            Expression::FunctionCall(call) => self.infer_function_call(call),
+            Expression::Try(try_expr) => self.infer_try(try_expr),
+            Expression::Await(await_expr) => self.infer_await(await_expr),
             Expression::BinaryOperation(binop) => self.infer_binary_op(binop),
+            Expression::ChainedComparison(chain) => self.infer_chained_comparison(chain),
             Expression::Assignment(assign) => self.infer_assignment(assign),
+            Expression::NamedExpression(named) => self.infer_named_expression(named),
+            Expression::LetCondition(condition) => self.infer_let_condition(condition),
+            Expression::ListComprehension(comprehension) => self.infer_list_comprehension(comprehension),
             Expression::UnaryOperation(unop) => self.infer_unary_op(unop),
+            Expression::TypeCast(cast) => self.infer_type_cast(cast),
+            Expression::Conditional(conditional) => self.infer_conditional(conditional),
+            Expression::Block(block) => self.infer_block(block),
+            Expression::Array(array) => self.infer_array(array),
+            Expression::IndexAccess(access) => self.infer_index(&access.array, &access.index),
+            Expression::ArraySlice(slice) => self.infer_slice(slice),
+            Expression::StructLiteral(literal) => self.infer_struct_literal(literal),
+            Expression::Tuple(tuple) => Ok(Type::Tuple(
+                tuple.elements.iter().map(|element| self.infer_expression(element)).collect::<Result<_, _>>()?,
+            )),
+            Expression::TupleAccess(access) => self.infer_tuple_access(access),
+            Expression::LambdaExpression(lambda) => self.infer_closure(lambda),
+            Expression::DictAccess(access) => self.infer_index(&access.dict, &access.key),
+            Expression::CollectionAccess(
+                CollectionAccess::Array(receiver, key)
+                | CollectionAccess::Dict(receiver, key)
+                | CollectionAccess::Unknown(receiver, key),
+            ) => self.infer_index(receiver, key),
             _ => Ok(Type::Infer), // Pour les autres cas
         }
     }
@@ -63,7 +749,8 @@ impl TypeContext {
 
         match binop.operator {
             Operator::Addition | Operator::Substraction |
-            Operator::Multiplication | Operator::Division => {
+            Operator::Multiplication | Operator::Division |
+            Operator::Power | Operator::FloorDivision => {
                 if left_type == Type::Int && right_type == Type::Int {
                     Ok(Type::Int)
                 } else if left_type == Type::Float || right_type == Type::Float {
@@ -76,14 +763,116 @@ impl TypeContext {
             },
             Operator::Equal | Operator::NotEqual |
             Operator::LessThan | Operator::GreaterThan |
-            Operator::LesshanOrEqual | Operator::GreaterThanOrEqual => {
-                self.add_constraint(TypeConstraint::Equal(left_type, right_type));
+            Operator::LesshanOrEqual | Operator::GreaterThanOrEqual |
+            Operator::In | Operator::NotIn |
+            Operator::Is | Operator::IsNot => {
+                self.infer_comparison(left_type, &binop.operator, right_type)
+            },
+            Operator::And | Operator::Or => {
+                for operand_type in [&left_type, &right_type] {
+                    if *operand_type != Type::Bool && *operand_type != Type::Infer {
+                        return Err(format!("Logical operator expects booleans, found {:?}", operand_type));
+                    }
+                }
                 Ok(Type::Bool)
             },
+            // operateurs bit a bit et decalages : entiers uniquement
+            Operator::BitwiseAnd | Operator::BitwiseOr | Operator::BitwiseXor |
+            Operator::LeftShift | Operator::RightShift => {
+                for operand_type in [&left_type, &right_type] {
+                    if *operand_type != Type::Int && *operand_type != Type::Infer {
+                        return Err(format!("Bitwise operator expects integers, found {:?}", operand_type));
+                    }
+                }
+                Ok(Type::Int)
+            },
             _ => Ok(Type::Infer),
         }
     }
 
+    /// Type d'un maillon de comparaison `left op right`, toujours bool
+    fn infer_comparison(&mut self, left_type: Type, operator: &Operator, right_type: Type) -> Result<Type, String> {
+        match operator {
+            Operator::In | Operator::NotIn => {
+                let element_type = self.membership_type(&right_type)?;
+                // dans une chaine on cherche une sous-chaine ou un caractere
+                let element_type = if element_type == Type::String && left_type == Type::Char {
+                    Type::Char
+                } else {
+                    element_type
+                };
+                if left_type != Type::Infer && element_type != Type::Infer && left_type != element_type {
+                    return Err(format!(
+                        "Type mismatch in membership test: {:?} in container of {:?}",
+                        left_type, element_type
+                    ));
+                }
+                Ok(Type::Bool)
+            },
+            // l'identite compare les references, quel que soit le type
+            Operator::Is | Operator::IsNot => Ok(Type::Bool),
+            _ => {
+                let numeric = |ty: &Type| matches!(ty, Type::Int | Type::Float);
+                if left_type != Type::Infer && right_type != Type::Infer
+                    && left_type != right_type && !(numeric(&left_type) && numeric(&right_type)) {
+                    return Err(format!(
+                        "Type mismatch in comparison: {:?} and {:?}",
+                        left_type, right_type
+                    ));
+                }
+                self.add_constraint(TypeConstraint::Equal(left_type, right_type));
+                Ok(Type::Bool)
+            },
+        }
+    }
+
+    /// `a < b <= c` : chaque maillon est verifie separement, le type de chaque operande
+    /// n'est infere qu'une fois comme son evaluation
+    fn infer_chained_comparison(&mut self, chain: &ChainedComparison) -> Result<Type, String> {
+        let mut operand_types = Vec::with_capacity(chain.operands.len());
+        for operand in &chain.operands {
+            operand_types.push(self.infer_expression(operand)?);
+        }
+
+        for (index, operator) in chain.operators.iter().enumerate() {
+            self.infer_comparison(operand_types[index].clone(), operator, operand_types[index + 1].clone())?;
+        }
+        Ok(Type::Bool)
+    }
+
+    /// Protocole de containment: type des elements qu'on peut chercher avec 'in' ou parcourir avec 'for'
+    /// - Array<T>, List<T>, Set<T>, Range<T> : T
+    /// - Dict<K, V>, HashMap<K, V> : K (on cherche parmi les cles)
+    /// - str : str (sous-chaine) ou char
+    ///
+    /// Un type utilisateur donne Infer ; seul 'for' l'accepte (voir `membership_type`)
+    fn contained_type(&self, container: &Type) -> Result<Type, String> {
+        match container {
+            Type::Array(element) => Ok((**element).clone()),
+            Type::String => Ok(Type::String),
+            Type::Generic(generic) => match (generic.base.as_str(), generic.type_parameters.first()) {
+                (base, Some(element)) if CONTAINERS.contains(&base) => Ok(element.clone()),
+                _ => Ok(Type::Infer),
+            },
+            Type::Named(_) | Type::Custom(_) | Type::Infer => Ok(Type::Infer),
+            other => Err(format!("Type {:?} does not support membership tests", other)),
+        }
+    }
+
+    /// Type des elements cherches par 'in' : les types utilisateur n'ont pas de protocole
+    /// de containment, un test d'appartenance sur eux est refuse
+    fn membership_type(&self, container: &Type) -> Result<Type, String> {
+        match container {
+            Type::Named(name) | Type::Custom(name) => {
+                Err(format!("Type '{}' does not support membership tests", name))
+            }
+            Type::Generic(generic) if !CONTAINERS.contains(&generic.base.as_str()) => {
+                Err(format!("Type '{}' does not support membership tests", generic.base))
+            }
+            _ => self.contained_type(container),
+        }
+    }
+
     fn infer_assignment(&mut self, assign: &Assignment) -> Result<Type, String> {
         let value_type = self.infer_expression(&assign.value)?;
 
@@ -96,6 +885,71 @@ impl TypeContext {
         }
     }
 
+    /// `name := value` lie `name` dans la portee de la fonction, y compris depuis une comprehension
+    fn infer_named_expression(&mut self, named: &NamedExpression) -> Result<Type, String> {
+        if self.comprehension_targets.contains(&named.name) {
+            return Err(format!(
+                "Assignment expression cannot rebind comprehension iteration variable '{}'",
+                named.name
+            ));
+        }
+        let value_type = self.infer_expression(&named.value)?;
+        self.type_vars.insert(named.name.clone(), value_type.clone());
+        Ok(value_type)
+    }
+
+    /// Les variables d'iteration sont locales a la comprehension ; les ':=' des conditions
+    /// et de l'element restent visibles apres elle
+    fn infer_list_comprehension(&mut self, comprehension: &ListComprehension) -> Result<Type, String> {
+        let mut targets = Vec::new();
+        for iterator in &comprehension.iterators {
+            pattern_names(&iterator.pattern, &mut targets);
+        }
+        let shadowed: Vec<(String, Option<Type>)> = targets
+            .iter()
+            .map(|name| (name.clone(), self.type_vars.get(name).cloned()))
+            .collect();
+        let enclosing_targets = self.comprehension_targets.len();
+        self.comprehension_targets.extend(targets);
+
+        let result = self.infer_comprehension_body(comprehension);
+
+        self.comprehension_targets.truncate(enclosing_targets);
+        for (name, previous) in shadowed {
+            match previous {
+                Some(previous) => self.type_vars.insert(name, previous),
+                None => self.type_vars.remove(&name),
+            };
+        }
+        result
+    }
+
+    fn infer_comprehension_body(&mut self, comprehension: &ListComprehension) -> Result<Type, String> {
+        for iterator in &comprehension.iterators {
+            let iterable_type = self.infer_expression(&iterator.iterator)?;
+            let element_type = self.contained_type(&iterable_type)?;
+            if let Pattern::Identifier(name) = &iterator.pattern {
+                self.type_vars.insert(name.clone(), element_type);
+            } else {
+                let mut names = Vec::new();
+                pattern_names(&iterator.pattern, &mut names);
+                for name in names {
+                    self.type_vars.insert(name, Type::Infer);
+                }
+            }
+        }
+
+        for condition in &comprehension.conditions {
+            let condition_type = self.infer_expression(condition)?;
+            if condition_type != Type::Bool && condition_type != Type::Infer {
+                return Err(format!("Comprehension condition must be a boolean, found {:?}", condition_type));
+            }
+        }
+
+        let element_type = self.infer_expression(&comprehension.elements)?;
+        Ok(Type::Array(Box::new(element_type)))
+    }
+
     fn infer_variable_declaration(&mut self, decl: &VariableDeclaration)
                                   -> Result<Type, String> {
         let inferred_type = if let Some(ref expr) = decl.value {
@@ -104,16 +958,18 @@ impl TypeContext {
             Type::Infer
         };
 
-        if let Some(ref explicit_type) = decl.variable_type {
-            if explicit_type != &Type::Infer && explicit_type != &inferred_type {
-                return Err(format!(
-                    "Type mismatch: expected {:?}, found {:?}",
-                    explicit_type, inferred_type
-                ));
+        // le parseur note `let x = ...` sans annotation avec le type Infer
+        match decl.variable_type {
+            Some(ref explicit_type) if *explicit_type != Type::Infer => {
+                if !types_compatible(explicit_type, &inferred_type) {
+                    return Err(format!(
+                        "Type mismatch: expected {:?}, found {:?}",
+                        explicit_type, inferred_type
+                    ));
+                }
+                Ok(explicit_type.clone())
             }
-            Ok(explicit_type.clone())
-        } else {
-            Ok(inferred_type)
+            _ => Ok(inferred_type),
         }
     }
 
@@ -136,6 +992,13 @@ impl TypeContext {
                     _ => Err("Operator '!' can only be applied to boolean types".to_string())
                 }
             },
+            UnaryOperator::BitwiseNot => {
+                match operand_type {
+                    Type::Int => Ok(Type::Int),
+                    Type::Infer => Ok(Type::Infer),
+                    _ => Err("Operator '~' can only be applied to integer types".to_string())
+                }
+            },
             UnaryOperator::Reference => Ok(Type::Array(Box::new(operand_type))),
             UnaryOperator::ReferenceMutable => Ok(Type::Array(Box::new(operand_type))),
             _ => todo!(),
@@ -146,6 +1009,264 @@ impl TypeContext {
 
 
 
+    fn infer_type_cast(&mut self, cast: &TypeCast) -> Result<Type, String> {
+        let source_type = self.infer_expression(&cast.expression)?;
+        check_cast(&source_type, &cast.target_type)
+    }
+
+    /// Constructeurs integres : Some(x), Ok(x), Err(e), et fonctions declarees
+    fn infer_function_call(&mut self, call: &FunctionCall) -> Result<Type, String> {
+        // appel d'une valeur : variable contenant une fermeture, `make_adder(1)(2)`...
+        let callee_type = match &*call.name {
+            Expression::Identifier(name) if !self.type_vars.contains_key(name) => None,
+            callee => Some(self.infer_expression(callee)?),
+        };
+        if let Some(Type::Function(parameter_types, return_type)) = callee_type {
+            return self.infer_closure_call(call, &parameter_types, *return_type);
+        }
+
+        let Expression::Identifier(name) = &*call.name else { return Ok(Type::Infer) };
+        if let Some(call_type) = self.functions.get(name).cloned() {
+            if let Some(parameters) = self.signatures.get(name).cloned() {
+                self.check_call_arguments(name, &parameters, call)?;
+            }
+            return Ok(call_type);
+        }
+        if BuiltinEnum::from_variant(name).is_none() || self.type_vars.contains_key(name) {
+            return Ok(Type::Infer);
+        }
+        if call.arguments.len() != 1 {
+            return Err(format!("{} expects 1 argument, found {}", name, call.arguments.len()));
+        }
+
+        let value_type = self.infer_expression(&call.arguments[0])?;
+        match name.as_str() {
+            "Some" => Ok(option_type(value_type)),
+            "Ok" => Ok(result_type(value_type, Type::Infer)),
+            _ => Ok(result_type(Type::Infer, value_type)),
+        }
+    }
+
+    /// Lie les arguments d'un appel aux parametres (voir `semantic::arguments`) et verifie leurs types
+    fn check_call_arguments(&mut self, name: &str, parameters: &[ParameterSpec], call: &FunctionCall) -> Result<(), String> {
+        let bindings = bind_arguments(name, parameters, &call.arguments, &call.keyword_arguments)?;
+        for (parameter, binding) in parameters.iter().zip(bindings) {
+            let values: Vec<&Expression> = match binding {
+                Binding::Argument(value) => vec![value],
+                Binding::Default => Vec::new(),
+                Binding::Variadic(values) => values,
+                Binding::KeywordVariadic(keywords) => keywords.iter().map(|keyword| &keyword.value).collect(),
+            };
+            for value in values {
+                let value_type = self.infer_expression(value)?;
+                if !types_compatible(&parameter.parameter_type, &value_type) {
+                    return Err(format!(
+                        "Mismatched argument type for '{}': expected {:?}, found {:?}",
+                        parameter.name, parameter.parameter_type, value_type
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn infer_closure_call(&mut self, call: &FunctionCall, parameter_types: &[Type], return_type: Type) -> Result<Type, String> {
+        if call.arguments.len() != parameter_types.len() {
+            return Err(format!(
+                "Closure expects {} arguments, found {}",
+                parameter_types.len(),
+                call.arguments.len()
+            ));
+        }
+        for (argument, expected) in call.arguments.iter().zip(parameter_types) {
+            let argument_type = self.infer_expression(argument)?;
+            if !types_compatible(expected, &argument_type) {
+                return Err(format!("Mismatched argument type: expected {:?}, found {:?}", expected, argument_type));
+            }
+        }
+        Ok(return_type)
+    }
+
+    /// Type d'une fermeture : `fn(parametres) -> retour` ; un corps reduit a une expression
+    /// donne le type de retour, un bloc est verifie contre le type de retour declare
+    fn infer_closure(&mut self, lambda: &LambdaExpression) -> Result<Type, String> {
+        let parameter_types: Vec<Type> = lambda.parameters.iter().map(|parameter| parameter.parameter_type.clone()).collect();
+        let declared_return = lambda.return_type.clone().unwrap_or(Type::Infer);
+
+        let enclosing_vars = self.type_vars.clone();
+        for parameter in &lambda.parameters {
+            self.type_vars.insert(parameter.name.clone(), parameter.parameter_type.clone());
+        }
+        let return_type = match lambda.body.as_slice() {
+            [ASTNode::Expression(body)] => self.infer_expression(body).and_then(|body_type| {
+                if types_compatible(&declared_return, &body_type) {
+                    Ok(if declared_return == Type::Infer { body_type } else { declared_return.clone() })
+                } else {
+                    Err(format!("Mismatched closure return type: expected {:?}, found {:?}", declared_return, body_type))
+                }
+            }),
+            body => {
+                let enclosing_return = self.return_type.replace(declared_return.clone());
+                let enclosing_yield = self.yield_type.take();
+                let result = self.check_block(body);
+                self.return_type = enclosing_return;
+                self.yield_type = enclosing_yield;
+                result.map(|_| declared_return)
+            }
+        };
+        self.type_vars = enclosing_vars;
+        Ok(Type::Function(parameter_types, Box::new(return_type?)))
+    }
+
+    fn infer_await(&mut self, await_expr: &AwaitExpression) -> Result<Type, String> {
+        if !self.in_async {
+            return Err("'await' is only allowed inside an async function".to_string());
+        }
+        let awaited_type = self.infer_expression(&await_expr.expression)?;
+        check_await(&awaited_type)
+    }
+
+    fn infer_try(&mut self, try_expr: &TryExpression) -> Result<Type, String> {
+        let operand_type = self.infer_expression(&try_expr.expression)?;
+        check_try(&operand_type, self.return_type.as_ref())
+    }
+
+    fn infer_conditional(&mut self, conditional: &Conditional) -> Result<Type, String> {
+        let condition_type = self.infer_expression(&conditional.condition)?;
+        if condition_type != Type::Bool && condition_type != Type::Infer {
+            return Err(format!("Condition must be a boolean, found {:?}", condition_type));
+        }
+
+        let then_type = self.infer_expression(&conditional.then_block)?;
+        let else_type = self.infer_expression(&conditional.else_block)?;
+        match (then_type, else_type) {
+            (Type::Infer, other) | (other, Type::Infer) => Ok(other),
+            (then_type, else_type) if then_type == else_type => Ok(then_type),
+            (then_type, else_type) => Err(format!(
+                "Type mismatch in conditional branches: {:?} and {:?}",
+                then_type, else_type
+            )),
+        }
+    }
+
+    /// `[a, b, c]` : tous les elements doivent avoir le meme type
+    fn infer_array(&mut self, array: &ArrayExpression) -> Result<Type, String> {
+        let mut element_type = Type::Infer;
+        for element in &array.elements {
+            let current = self.infer_expression(element)?;
+            if element_type == Type::Infer {
+                element_type = current;
+            } else if !types_compatible(&element_type, &current) {
+                return Err(format!(
+                    "Array elements must have the same type: {:?} and {:?}",
+                    element_type, current
+                ));
+            }
+        }
+        Ok(Type::Array(Box::new(element_type)))
+    }
+
+    fn infer_index(&mut self, receiver: &Expression, key: &Expression) -> Result<Type, String> {
+        let receiver_type = self.infer_expression(receiver)?;
+        let key_type = self.infer_expression(key)?;
+        index_type(&receiver_type, &key_type)
+    }
+
+    fn infer_slice(&mut self, slice: &ArraySlice) -> Result<Type, String> {
+        if let Some(step) = &slice.step {
+            if let Expression::Literal(Literal::Integer { value }) = &**step {
+                if value.sign() == num_bigint::Sign::NoSign {
+                    return Err("Slice step cannot be zero".to_string());
+                }
+            }
+        }
+        let receiver_type = self.infer_expression(&slice.array)?;
+        let mut bounds = [None, None, None];
+        for (bound, expression) in bounds.iter_mut().zip([&slice.start, &slice.end, &slice.step]) {
+            if let Some(expression) = expression {
+                *bound = Some(self.infer_expression(expression)?);
+            }
+        }
+        slice_type(&receiver_type, [bounds[0].as_ref(), bounds[1].as_ref(), bounds[2].as_ref()])
+    }
+
+    /// `Point { x: 1, ..p }` : chaque champ doit exister et avoir le type declare ; sans base,
+    /// tous les champs doivent etre fournis
+    fn infer_struct_literal(&mut self, literal: &StructLiteral) -> Result<Type, String> {
+        let fields = self
+            .structs
+            .get(&literal.name)
+            .cloned()
+            .ok_or_else(|| format!("Unknown struct '{}'", literal.name))?;
+        let struct_type = Type::Named(literal.name.clone());
+
+        let mut provided: Vec<&str> = Vec::new();
+        for field in &literal.fields {
+            if provided.contains(&field.name.as_str()) {
+                return Err(format!("Field '{}' of '{}' is specified more than once", field.name, literal.name));
+            }
+            provided.push(&field.name);
+            let (_, declared_type) = fields
+                .iter()
+                .find(|(name, _)| *name == field.name)
+                .ok_or_else(|| format!("Struct '{}' has no field '{}'", literal.name, field.name))?;
+            let value_type = self.infer_expression(&field.value)?;
+            if !types_compatible(declared_type, &value_type) {
+                return Err(format!(
+                    "Field '{}' of '{}' expects {:?}, found {:?}",
+                    field.name, literal.name, declared_type, value_type
+                ));
+            }
+        }
+
+        match &literal.base {
+            Some(base) => {
+                let base_type = self.infer_expression(base)?;
+                if !types_compatible(&struct_type, &base_type) {
+                    return Err(format!(
+                        "Base of '{}' literal must be {:?}, found {:?}",
+                        literal.name, struct_type, base_type
+                    ));
+                }
+            }
+            None => {
+                let missing: Vec<&str> = fields
+                    .iter()
+                    .map(|(name, _)| name.as_str())
+                    .filter(|name| !provided.contains(name))
+                    .collect();
+                if !missing.is_empty() {
+                    return Err(format!("Missing fields in '{}' literal: {}", literal.name, missing.join(", ")));
+                }
+            }
+        }
+        Ok(struct_type)
+    }
+
+    /// `t.0` : type de l'element a cette position
+    fn infer_tuple_access(&mut self, access: &TupleAccess) -> Result<Type, String> {
+        match self.infer_expression(&access.tuple)? {
+            Type::Infer => Ok(Type::Infer),
+            Type::Tuple(element_types) => element_types.get(access.index).cloned().ok_or_else(|| {
+                format!("Tuple {:?} has no field {}", Type::Tuple(element_types.clone()), access.index)
+            }),
+            other => Err(format!("Type {:?} has no field {}", other, access.index)),
+        }
+    }
+
+    // la valeur d'un bloc est celle de sa derniere expression
+    fn infer_block(&mut self, block: &BlockExpression) -> Result<Type, String> {
+        let Some((tail, statements)) = block.statements.split_last() else { return Ok(Type::Infer) };
+        // les variables declarees dans le bloc ne lui survivent pas
+        let enclosing_vars = self.type_vars.clone();
+        let block_type = self.check_block(statements).and_then(|()| match tail {
+            ASTNode::Expression(expr) | ASTNode::Statement(Statement::Expression(expr)) => self.infer_expression(expr),
+            node => self.check_node(node).map(|()| Type::Infer),
+        });
+        self.type_vars = enclosing_vars;
+        block_type
+    }
+
     fn add_constraint(&mut self, constraint: TypeConstraint) {
         self.constraints.push(constraint);
     }
@@ -160,3 +1281,74 @@ impl TypeContext {
 
 
 }
+
+/// `Infer` est compatible avec tout ; `Option<T>` et `Result<T, E>` comparent leurs parametres un a un
+fn types_compatible(expected: &Type, found: &Type) -> bool {
+    match (expected, found) {
+        (Type::Infer, _) | (_, Type::Infer) => true,
+        (Type::Generic(expected), Type::Generic(found)) => {
+            expected.base == found.base
+                && expected.type_parameters.len() == found.type_parameters.len()
+                && expected
+                    .type_parameters
+                    .iter()
+                    .zip(&found.type_parameters)
+                    .all(|(expected, found)| types_compatible(expected, found))
+        }
+        (Type::Function(expected_parameters, expected_return), Type::Function(found_parameters, found_return)) => {
+            expected_parameters.len() == found_parameters.len()
+                && expected_parameters
+                    .iter()
+                    .zip(found_parameters)
+                    .all(|(expected, found)| types_compatible(expected, found))
+                && types_compatible(expected_return, found_return)
+        }
+        (Type::Tuple(expected), Type::Tuple(found)) => {
+            expected.len() == found.len()
+                && expected.iter().zip(found).all(|(expected, found)| types_compatible(expected, found))
+        }
+        (expected, found) => expected == found,
+    }
+}
+
+/// Noms lies par un motif de boucle `for`, de `let` ou d'`if let`
+pub(crate) fn pattern_names(pattern: &Pattern, names: &mut Vec<String>) {
+    match pattern {
+        Pattern::Identifier(name) | Pattern::MutableIdentifier(name) => names.push(name.clone()),
+        Pattern::Binding(name, pattern) => {
+            names.push(name.clone());
+            pattern_names(pattern, names);
+        }
+        // toutes les alternatives lient les memes noms
+        Pattern::Or(alternatives) => {
+            if let Some(first) = alternatives.first() {
+                pattern_names(first, names);
+            }
+        }
+        Pattern::Struct(pattern) => {
+            for field in &pattern.fields {
+                pattern_names(&field.pattern, names);
+            }
+        }
+        Pattern::Tuple(patterns)
+        | Pattern::Array(patterns)
+        | Pattern::TupleRest(patterns)
+        | Pattern::Constructor(_, patterns) => {
+            for pattern in patterns {
+                pattern_names(pattern, names);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Type des valeurs produites par un generateur declare `-> Iterator<T>` (ou sans type de retour)
+fn generator_item(return_type: &Type) -> Option<Type> {
+    match return_type {
+        Type::Infer => Some(Type::Infer),
+        Type::Generic(generic) if generic.base == "Iterator" || generic.base == "Generator" => {
+            Some(generic.type_parameters.first().cloned().unwrap_or(Type::Infer))
+        }
+        _ => None,
+    }
+}
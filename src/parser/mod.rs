@@ -1,11 +1,13 @@
 pub mod ast;
 
+pub mod cst;
+
 pub mod parser_error;
 
 pub mod parser;
 mod parser_1;
 mod test_main;
-mod inference;
+pub mod inference;
 mod synchronizer;
 mod statements;
 mod declarations;
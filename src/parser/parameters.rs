@@ -1,7 +1,7 @@
-use crate::parser::ast::{GenericParameter, ImplMethod, Parameter, SelfKind, Type, TypeBound, Visibility};
+use crate::parser::ast::{Expression, GenericParameter, ImplMethod, Parameter, ParameterKind, SelfKind, Type, TypeBound, Visibility};
 use crate::parser::parser::Parser;
 use crate::parser::parser_error::{ParserError, ParserErrorType};
-use crate::parser::parser_error::ParserErrorType::{ExpectedLifetime, InvalidConstructorName, MissingType};
+use crate::parser::parser_error::ParserErrorType::{ExpectedLifetime, InvalidConstructorName, InvalidParameterOrder, MissingType};
 use crate::tok::{Delimiters, Keywords, Operators, TokenType};
 
 impl Parser{
@@ -210,6 +210,7 @@ impl Parser{
                     break;
                 }
             }
+            self.check_parameter_order(&parameters)?;
         }
 
         self.consume(TokenType::DELIMITER(Delimiters::RPAR))?;
@@ -243,10 +244,63 @@ impl Parser{
         }
     }
 
+    /// `*args` ou `**kwargs` : consomme l'etoile et donne la sorte de parametre
+    pub(crate) fn parse_parameter_kind(&mut self) -> ParameterKind {
+        if self.match_token(&[TokenType::OPERATOR(Operators::STAR)]) {
+            ParameterKind::Variadic
+        } else if self.match_token(&[TokenType::OPERATOR(Operators::DOUBLESTAR)]) {
+            ParameterKind::KeywordVariadic
+        } else {
+            ParameterKind::Regular
+        }
+    }
+
+    /// valeur par defaut `= expr` apres le type d'un parametre
+    pub(crate) fn parse_default_value(&mut self) -> Result<Option<Expression>, ParserError> {
+        if self.match_token(&[TokenType::OPERATOR(Operators::EQUAL)]) {
+            Ok(Some(self.parse_expression(0)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Ordre a la Python : un parametre sans defaut ne suit pas un parametre avec defaut
+    /// (sauf apres `*args`, ou les parametres ne sont plus que nommes), au plus un `*args`
+    /// et un `**kwargs`, `**kwargs` en dernier, pas de defaut pour les variadiques
+    pub(crate) fn check_parameter_order(&self, parameters: &[Parameter]) -> Result<(), ParserError> {
+        let mut seen_default = false;
+        let mut seen_variadic = false;
+        let mut seen_keyword_variadic = false;
+        for parameter in parameters {
+            let valid = !seen_keyword_variadic
+                && match parameter.kind {
+                    ParameterKind::Regular => {
+                        let valid = seen_variadic || parameter.default_value.is_some() || !seen_default;
+                        seen_default |= parameter.default_value.is_some();
+                        valid
+                    }
+                    ParameterKind::Variadic => {
+                        let valid = !seen_variadic && parameter.default_value.is_none();
+                        seen_variadic = true;
+                        valid
+                    }
+                    ParameterKind::KeywordVariadic => {
+                        seen_keyword_variadic = true;
+                        parameter.default_value.is_none()
+                    }
+                };
+            if !valid {
+                return Err(ParserError::new(InvalidParameterOrder, self.current_position()));
+            }
+        }
+        Ok(())
+    }
+
     fn parse_parameter(&mut self) -> Result<Parameter, ParserError> {
         println!("Début du parsing d'un paramètre");
 
         // 1. Parser le nom du paramètre
+        let kind = self.parse_parameter_kind();
         let param_name = self.consume_identifier()?;
 
         // 2. Si on trouve un deux-points, on doit avoir un type qui suit
@@ -264,12 +318,16 @@ impl Parser{
             Ok(Parameter {
                 name: param_name,
                 parameter_type: param_type,
+                default_value: self.parse_default_value()?,
+                kind,
             })
         } else {
             // Si pas de ':', utiliser le type Infer
             Ok(Parameter {
                 name: param_name,
                 parameter_type: Type::Infer,
+                default_value: self.parse_default_value()?,
+                kind,
             })
         }
     }
@@ -2,7 +2,7 @@
 #[allow(dead_code)]
 use crate::lexer::lex::{SyntaxMode, Token};
 
-use crate::parser::ast::{ ArrayRest, AssociatedType, ASTNode,  CompoundOperator, Expression,  GenericType,   ImportKeyword, Literal,  MatchArm, MatchStatement, ModuleImportStatement, Operator, Parameter, Pattern,  RangePattern, ReturnStatement,  SpecificImportStatement, Statement, Type, TypeBound,UnaryOperation, UnaryOperator,};
+use crate::parser::ast::{ ArrayRest, AssociatedType, ASTNode,  CompoundOperator, Expression, FieldPattern, GenericType,   ImportKeyword, Literal,  MatchArm, MatchStatement, ModuleImportStatement, Operator, Parameter, Pattern,  RangePattern, ReturnStatement,  SpecificImportStatement, Statement, StructPattern, WildcardImportStatement, Type, TypeBound,UnaryOperation, UnaryOperator,};
 
 use crate::parser::parser_error::ParserErrorType::{ ExpectIdentifier, ExpectedTypeAnnotation,  InvalidTypeAnnotation,  UnexpectedEOF, UnexpectedEndOfInput,  UnexpectedToken, ExpectedParameterName,MultipleRestPatterns, ExpectedUseOrImport,  ExpectedCommaOrCloseBrace, };
 use crate::parser::parser_error::{ParserError, ParserErrorType, Position};
@@ -19,6 +19,8 @@ pub struct Parser {
     pub(crate) current: usize,     // index du token actuel
     pub(crate) syntax_mode: SyntaxMode,
     indent_level: Vec<usize>,
+    pub(crate) allow_as_cast: bool, // faux dans les contextes ou 'as' introduit un alias (with, except)
+    pub(crate) allow_struct_literal: bool, // faux dans les conditions en mode accolades, ou '{' ouvre le bloc
 }
 
 
@@ -29,6 +31,8 @@ impl Parser {
             current: 0,
             syntax_mode,
             indent_level: vec![0],
+            allow_as_cast: true,
+            allow_struct_literal: true,
         }
     }
 
@@ -197,14 +201,16 @@ impl Parser {
 
         if !self.match_token(&[TokenType::DELIMITER(Delimiters::RPAR)]) {
             loop {
+                let kind = self.parse_parameter_kind();
                 //let name = self.consume_parameter_name()?;
                 let name = self.consume_identifier()?;
                 println!("Nom du paramètre parsé : {}", name);
                 self.consume(TokenType::DELIMITER(Delimiters::COLON))?;
                 let param_type = self.parse_type()?;
                 println!("Type du paramètre parsé : {:?}", param_type);
+                let default_value = self.parse_default_value()?;
 
-                parameters.push(Parameter { name, parameter_type: param_type });
+                parameters.push(Parameter { name, parameter_type: param_type, default_value, kind });
 
                 if self.match_token(&[TokenType::DELIMITER(Delimiters::COMMA)]) {
                     continue;
@@ -216,6 +222,7 @@ impl Parser {
                 }
             }
         }
+        self.check_parameter_order(&parameters)?;
         println!("Paramètres parsés : {:?}", parameters);
         Ok(parameters)
     }
@@ -270,6 +277,10 @@ impl Parser {
 
         let mut body = Vec::new();
         while !self.check(&[TokenType::DELIMITER(Delimiters::RCURBRACE)]){
+            // `return x;` laisse son point-virgule
+            if self.match_token(&[TokenType::DELIMITER(Delimiters::SEMICOLON)]) {
+                continue;
+            }
             let statement = self.parse_statement()?;
             body.push(statement);
         }
@@ -371,6 +382,13 @@ impl Parser {
                         if self.check(&[TokenType::OPERATOR(Operators::GREATER)]) {
                             self.advance();
                             break;
+                        } else if self.check(&[TokenType::OPERATOR(Operators::RIGHTSHIFT)]) {
+                            // Option<Result<T, E>> : '>>' ferme deux listes, on garde le second '>'
+                            let token = &mut self.tokens[self.current];
+                            token.text = ">".to_string();
+                            token.token_type = TokenType::OPERATOR(Operators::GREATER);
+                            token.column += 1;
+                            break;
                         } else if self.check(&[TokenType::DELIMITER(Delimiters::COMMA)]) {
                             self.advance();
                         } else {
@@ -386,6 +404,38 @@ impl Parser {
                     Ok(Type::Named(base_name))
                 }
             }
+            // type fonction : fn(int, str) -> int ; sans '->', renvoie ()
+            TokenType::KEYWORD(Keywords::FN) => {
+                self.advance();
+                self.consume(TokenType::DELIMITER(Delimiters::LPAR))?;
+                let mut parameter_types = Vec::new();
+                while !self.check(&[TokenType::DELIMITER(Delimiters::RPAR)]) {
+                    parameter_types.push(self.parse_type()?);
+                    if !self.match_token(&[TokenType::DELIMITER(Delimiters::COMMA)]) {
+                        break;
+                    }
+                }
+                self.consume(TokenType::DELIMITER(Delimiters::RPAR))?;
+                let return_type = if self.match_token(&[TokenType::OPERATOR(Operators::RARROW)]) {
+                    self.parse_type()?
+                } else {
+                    Type::Tuple(Vec::new())
+                };
+                Ok(Type::Function(parameter_types, Box::new(return_type)))
+            }
+            // type tuple : (int, str), (int,) ou ()
+            TokenType::DELIMITER(Delimiters::LPAR) => {
+                self.advance();
+                let mut element_types = Vec::new();
+                while !self.check(&[TokenType::DELIMITER(Delimiters::RPAR)]) {
+                    element_types.push(self.parse_type()?);
+                    if !self.match_token(&[TokenType::DELIMITER(Delimiters::COMMA)]) {
+                        break;
+                    }
+                }
+                self.consume(TokenType::DELIMITER(Delimiters::RPAR))?;
+                Ok(Type::Tuple(element_types))
+            }
             _ => {
                 println!("Unexpected token: {:?}", token);
                 // Si le token actuel n'est pas un type valide, renvoyer une erreur
@@ -406,7 +456,7 @@ impl Parser {
     pub fn parse_match_statement(&mut self) -> Result<ASTNode, ParserError> {
         println!("Début du parsing de l'instruction match");
         self.consume(TokenType::KEYWORD(Keywords::MATCH))?;
-        let match_expr = self.parse_expression(0)?;
+        let match_expr = self.parse_condition_expression()?;
 
         let mut arms = Vec::new();
         if self.syntax_mode == SyntaxMode::Indentation {
@@ -535,16 +585,8 @@ impl Parser {
     }
 
     pub fn parse_pattern_complex(&mut self) -> Result<Pattern, ParserError>{
-        if self.check(&[TokenType::DELIMITER(Delimiters::DOT)]){
-            self.consume(TokenType::DELIMITER(Delimiters::DOT))?;
-            self.consume(TokenType::DELIMITER(Delimiters::DOT))?;
-            Ok(Pattern::Rest)
-        }else if self.check(&[TokenType::DELIMITER(Delimiters::LPAR)]){
-            self.parse_tuple_pattern()
-        }else if self.check(&[TokenType::DELIMITER(Delimiters::LSBRACKET)]){
-            self.parse_array_pattern()
-        }else { self.parse_pattern() }
-
+        // tuples, tableaux, `..` et alternatives sont geres par parse_pattern
+        self.parse_pattern()
     }
 
     pub fn parse_tuple_pattern(&mut self) -> Result<Pattern, ParserError> {
@@ -677,55 +719,154 @@ impl Parser {
     }
 
 
+    /// motif complet : alternatives `p1 | p2 | ...`
     pub fn parse_pattern(&mut self) -> Result<Pattern, ParserError> {
         println!("Début du parsing du pattern");
+        let first = self.parse_single_pattern()?;
+        if !self.check(&[TokenType::OPERATOR(Operators::VBAR)]) {
+            return Ok(first);
+        }
+        let mut alternatives = vec![first];
+        while self.match_token(&[TokenType::OPERATOR(Operators::VBAR)]) {
+            alternatives.push(self.parse_single_pattern()?);
+        }
+        Ok(Pattern::Or(alternatives))
+    }
 
-
+    /// motif sans alternative
+    pub fn parse_single_pattern(&mut self) -> Result<Pattern, ParserError> {
         if self.match_token(&[TokenType::OPERATOR(Operators::UNDERSCORE)]) {
             // Pattern par défaut '_'
-            Ok(Pattern::Wildcard)
-        } else if let Some(token) = self.current_token() {
-            match &token.token_type {
-                TokenType::IDENTIFIER { name } => {
-                    if name == "_" {
-                        self.advance();
-                        Ok(Pattern::Wildcard)
-                    } else {
-                        let identifier = name.clone();
+            return Ok(Pattern::Wildcard);
+        }
+        let Some(token) = self.current_token() else {
+            return Err(ParserError::new(UnexpectedEndOfInput, self.current_position()));
+        };
+        match &token.token_type {
+            TokenType::DELIMITER(Delimiters::LPAR) => self.parse_tuple_pattern(),
+            TokenType::DELIMITER(Delimiters::LSBRACKET) => self.parse_array_pattern(),
+            TokenType::OPERATOR(Operators::DOTDOT) => {
+                self.advance();
+                match self.current_token().map(|token| &token.token_type) {
+                    // `..rest` lie le reste du tableau
+                    Some(TokenType::IDENTIFIER { name }) => {
+                        let name = name.clone();
                         self.advance();
-                        Ok(Pattern::Identifier(identifier))
+                        Ok(Pattern::Binding(name, Box::new(Pattern::Rest)))
+                    }
+                    // `..5` : intervalle sans borne inferieure
+                    _ if self.at_pattern_number() => {
+                        let end = self.parse_pattern_literal()?;
+                        Ok(Pattern::RangePattern(RangePattern {
+                            start: None,
+                            end: Some(Box::new(Expression::Literal(end))),
+                            inclusive: false,
+                        }))
                     }
-                },
-                TokenType::INTEGER { value } => {
-                    let int_value = value.clone(); // Clonez la valeur ici
-                    self.advance(); // Consomme l'entier
-                    Ok(Pattern::Literal(Literal::Integer { value: int_value }))
-                },
-                TokenType::FLOAT { value } => {
-                    let float_value = *value;
-                    self.advance(); // Consomme le flottant
-                    Ok(Pattern::Literal(Literal::Float { value: float_value }))
-                },
-                TokenType::STRING { value, kind: _ } => {
-                    let string_value = value.clone();
-                    self.advance(); // Consomme la chaîne
-                    Ok(Pattern::Literal(Literal::String(string_value)))
+                    _ => Ok(Pattern::Rest),
+                }
+            }
+            TokenType::KEYWORD(Keywords::MUT) => {
+                self.advance();
+                Ok(Pattern::MutableIdentifier(self.consume_identifier()?))
+            }
+            TokenType::IDENTIFIER { name } if name == "_" => {
+                self.advance();
+                Ok(Pattern::Wildcard)
+            }
+            TokenType::IDENTIFIER { name } => {
+                let name = name.clone();
+                self.advance();
+                if self.match_token(&[TokenType::OPERATOR(Operators::AT)]) {
+                    // `n @ 1..=9`
+                    let pattern = self.parse_single_pattern()?;
+                    Ok(Pattern::Binding(name, Box::new(pattern)))
+                } else if self.check(&[TokenType::DELIMITER(Delimiters::LPAR)]) {
+                    // `Some(x)`
+                    let Pattern::Tuple(patterns) = self.parse_tuple_pattern()? else { unreachable!() };
+                    Ok(Pattern::Constructor(name, patterns))
+                } else if self.check(&[TokenType::DELIMITER(Delimiters::LCURBRACE)]) {
+                    self.parse_struct_pattern(name)
+                } else {
+                    Ok(Pattern::Identifier(name))
+                }
+            }
+            _ => {
+                let literal = self.parse_pattern_literal()?;
+                let inclusive = self.check(&[TokenType::OPERATOR(Operators::DOTDOTEQUAL)]);
+                if !self.match_token(&[TokenType::OPERATOR(Operators::DOTDOT), TokenType::OPERATOR(Operators::DOTDOTEQUAL)]) {
+                    return Ok(Pattern::Literal(literal));
                 }
-                TokenType::KEYWORD(Keywords::TRUE) => {
-                    self.advance(); // Consomme le mot-clé 'true'
-                    Ok(Pattern::Literal(Literal::Boolean(true)))
-                },
-                TokenType::KEYWORD(Keywords::FALSE) => {
-                    self.advance(); // Consomme le mot-clé 'false'
-                    Ok(Pattern::Literal(Literal::Boolean(false)))
-                },
+                // `1..5`, `1..=9`, `1..`
+                let end = if inclusive || self.at_pattern_number() {
+                    Some(Box::new(Expression::Literal(self.parse_pattern_literal()?)))
+                } else {
+                    None
+                };
+                Ok(Pattern::RangePattern(RangePattern {
+                    start: Some(Box::new(Expression::Literal(literal))),
+                    end,
+                    inclusive,
+                }))
+            }
+        }
+    }
 
+    fn at_pattern_number(&self) -> bool {
+        matches!(
+            self.current_token().map(|token| &token.token_type),
+            Some(TokenType::INTEGER { .. } | TokenType::FLOAT { .. } | TokenType::OPERATOR(Operators::MINUS))
+        )
+    }
 
-                _ => Err(ParserError::new(UnexpectedToken, self.current_position())),
+    /// litteral d'un motif, eventuellement negatif : `-1`, `2.5`, `"a"`, `true`
+    fn parse_pattern_literal(&mut self) -> Result<Literal, ParserError> {
+        let negative = self.match_token(&[TokenType::OPERATOR(Operators::MINUS)]);
+        let Some(token) = self.current_token() else {
+            return Err(ParserError::new(UnexpectedEndOfInput, self.current_position()));
+        };
+        let literal = match &token.token_type {
+            TokenType::INTEGER { value } if negative => Literal::Integer { value: -value.clone() },
+            TokenType::INTEGER { value } => Literal::Integer { value: value.clone() },
+            TokenType::FLOAT { value } if negative => Literal::Float { value: -*value },
+            TokenType::FLOAT { value } => Literal::Float { value: *value },
+            TokenType::STRING { value, kind: _ } if !negative => Literal::String(value.clone()),
+            TokenType::KEYWORD(Keywords::TRUE) if !negative => Literal::Boolean(true),
+            TokenType::KEYWORD(Keywords::FALSE) if !negative => Literal::Boolean(false),
+            _ => return Err(ParserError::new(UnexpectedToken, self.current_position())),
+        };
+        self.advance();
+        Ok(literal)
+    }
+
+    /// `Point { x, y: 0, .. }` ; `name` est deja consomme
+    fn parse_struct_pattern(&mut self, name: String) -> Result<Pattern, ParserError> {
+        println!("Début du parsing du pattern de structure");
+        self.consume(TokenType::DELIMITER(Delimiters::LCURBRACE))?;
+        let mut fields = Vec::new();
+        let mut has_rest = false;
+        while !self.check(&[TokenType::DELIMITER(Delimiters::RCURBRACE)]) {
+            if self.match_token(&[TokenType::OPERATOR(Operators::DOTDOT)]) {
+                // `..` doit etre le dernier element
+                has_rest = true;
+                break;
+            }
+            let field = self.consume_identifier()?;
+            let pattern = if self.match_token(&[TokenType::DELIMITER(Delimiters::COLON)]) {
+                self.parse_pattern()?
+            } else {
+                Pattern::Identifier(field.clone())
+            };
+            fields.push(FieldPattern { name: field, pattern });
+            if !self.match_token(&[TokenType::DELIMITER(Delimiters::COMMA)]) {
+                break;
             }
-        } else {
-            Err(ParserError::new(UnexpectedEndOfInput, self.current_position()))
         }
+        if !self.match_token(&[TokenType::DELIMITER(Delimiters::RCURBRACE)]) {
+            return Err(ParserError::new(ExpectedCommaOrCloseBrace, self.current_position()));
+        }
+        println!("Fin du parsing du pattern de structure OK!!!!!!!!!!!!!!!");
+        Ok(Pattern::Struct(StructPattern { name, fields, has_rest }))
     }
 
 
@@ -734,7 +875,12 @@ impl Parser {
         println!("Début du parsing de l'instruction de retour");
         self.consume(TokenType::KEYWORD(Keywords::RETURN))?;
         let value = if !self.match_token(&[TokenType::NEWLINE, TokenType::DEDENT, TokenType::EOF]) {
-            Some(self.parse_expression(0)?)
+            let value = self.parse_expression(0)?;
+            // fin de ligne, sans toucher au DEDENT qui ferme le bloc
+            if self.syntax_mode == SyntaxMode::Indentation {
+                self.match_token(&[TokenType::NEWLINE]);
+            }
+            Some(value)
         } else {
             None
         };
@@ -799,11 +945,60 @@ impl Parser {
 
     }
 
+    /// `from a.b import x, y as z`, `from a.b import (x, y)` ou `from a.b import *`
+    pub fn parse_from_import_statement(&mut self) -> Result<ASTNode, ParserError> {
+        println!("Début du parsing de l'instruction d'import From");
+
+        let module_path = self.parse_module_path()?;
+        self.consume(TokenType::KEYWORD(Keywords::IMPORT))?;
+
+        if self.match_token(&[TokenType::OPERATOR(Operators::STAR)]) {
+            self.consume_seperator();
+            println!("Fin du parsing de l'import From * OK!!!!!!!!!!!!!!");
+            return Ok(ASTNode::Statement(Statement::WildcardImportStatement(WildcardImportStatement{
+                keyword: ImportKeyword::From,
+                module_path,
+            })));
+        }
+
+        let parenthesized = self.match_token(&[TokenType::DELIMITER(Delimiters::LPAR)]);
+        let mut import_list = Vec::new();
+        loop {
+            let name = self.consume_identifier()?;
+            let alias = if self.match_token(&[TokenType::KEYWORD(Keywords::AS)]) {
+                Some(self.consume_identifier()?)
+            } else {
+                None
+            };
+            import_list.push((name, alias));
+
+            if !self.match_token(&[TokenType::DELIMITER(Delimiters::COMMA)]) {
+                break;
+            }
+            // virgule finale autorisee entre parentheses
+            if parenthesized && self.check(&[TokenType::DELIMITER(Delimiters::RPAR)]) {
+                break;
+            }
+        }
+        if parenthesized {
+            self.consume(TokenType::DELIMITER(Delimiters::RPAR))?;
+        }
+        self.consume_seperator();
+
+        println!("Fin du parsing de l'import From OK!!!!!!!!!!!!!!");
+        Ok(ASTNode::Statement(Statement::SpecificImportStatement(SpecificImportStatement{
+            keyword: ImportKeyword::From,
+            module_path,
+            alias: None,
+            imports: import_list,
+        })))
+    }
+
     pub fn parse_module_path(&mut self) -> Result<Vec<String>, ParserError> {
         let mut path = Vec::new();
         loop {
 
-            let name = self.consume_identifier()?;
+            let name = self.consume_path_segment()?;
             path.push(name);
 
             if self.match_token(&[TokenType::DELIMITER(Delimiters::DOT)]) {
@@ -898,19 +1093,61 @@ impl Parser {
 
 
 
+    /// Table de precedence des operateurs binaires, du plus faible au plus fort :
+    ///
+    /// | niveau | operateurs                                   | associativite |
+    /// |--------|----------------------------------------------|---------------|
+    /// | 1      | `or` `\|\|`                                   | gauche        |
+    /// | 2      | `and` `&&`                                   | gauche        |
+    /// | 3      | `==` `!=` `<` `>` `<=` `>=` `in` `not in` `is` `is not` | chainees |
+    /// | 4      | `..` `..=`                                   | gauche        |
+    /// | 5      | `\|`                                         | gauche        |
+    /// | 6      | `^`                                          | gauche        |
+    /// | 7      | `&`                                          | gauche        |
+    /// | 8      | `<<` `>>`                                    | gauche        |
+    /// | 9      | `+` `-`                                      | gauche        |
+    /// | 10     | `*` `/` `%` `//`                             | gauche        |
+    /// | 11     | `**`                                         | droite        |
+    ///
+    /// Les unaires (`-`, `!`, `~`, `&`) lient plus fort que tous les binaires sauf `**` :
+    /// `-2 ** 2` vaut `-(2 ** 2)`. `not` lie plus faiblement que les comparaisons :
+    /// `not a == b` vaut `not (a == b)`. Le cast `as` lie plus fort que tous les binaires.
+    /// Les comparaisons ne s'associent pas : `a < b <= c` donne un `ChainedComparison`.
+    /// La precedence 0 est reservee a l'expression complete (ternaire `a if c else b`).
     pub fn get_operator_precedence(&self, operator: &Operator) -> u8 {
         match operator {
-            Operator::Multiplication | Operator::Division | Operator::Modulo => 5,
-            Operator::Addition | Operator::Substraction => 4,
+            Operator::Power => 11,
+            Operator::Multiplication | Operator::Division | Operator::Modulo | Operator::FloorDivision => 10,
+            Operator::Addition | Operator::Substraction => 9,
+            Operator::LeftShift | Operator::RightShift => 8,
+            Operator::BitwiseAnd => 7,
+            Operator::BitwiseXor => 6,
+            Operator::BitwiseOr => 5,
+            Operator::Range | Operator::RangeInclusive => 4,
             Operator::LessThan | Operator::GreaterThan | Operator::LesshanOrEqual | Operator::GreaterThanOrEqual => 3,
-            Operator::Range | Operator::RangeInclusive => 3,
-            Operator::Equal | Operator::NotEqual => 2,
-            Operator::And => 1,
-            //Operator::Or => 0,
-            _ => 0,
+            Operator::Equal | Operator::NotEqual => 3,
+            Operator::In | Operator::NotIn | Operator::Is | Operator::IsNot => 3,
+            Operator::And => 2,
+            Operator::Or => 1,
         }
     }
 
+    /// Operateurs de comparaison (niveau 3) : ils se chainent, `a < b < c` vaut `a < b and b < c`
+    pub fn is_comparison_operator(&self, operator: &Operator) -> bool {
+        matches!(
+            operator,
+            Operator::Equal | Operator::NotEqual |
+            Operator::LessThan | Operator::GreaterThan |
+            Operator::LesshanOrEqual | Operator::GreaterThanOrEqual |
+            Operator::In | Operator::NotIn | Operator::Is | Operator::IsNot
+        )
+    }
+
+    /// `**` est le seul operateur binaire associatif a droite : `2 ** 3 ** 2` vaut `2 ** (3 ** 2)`
+    pub fn is_right_associative(&self, operator: &Operator) -> bool {
+        matches!(operator, Operator::Power)
+    }
+
 
 
     pub fn get_compound_operator(&self,op:&Operators) -> Option<CompoundOperator>{
@@ -920,6 +1157,13 @@ impl Parser {
             Operators::STAREQUAL => Some(CompoundOperator::MulAssign),
             Operators::SLASHEQUAL => Some(CompoundOperator::DivAssign),
             Operators::PERCENTEQUAL => Some(CompoundOperator::ModAssign),
+            Operators::AMPEREQUAL => Some(CompoundOperator::BitwiseAndAssign),
+            Operators::VBAREQUAL => Some(CompoundOperator::BitwiseOrAssign),
+            Operators::CIRCUMFLEXEQUAL => Some(CompoundOperator::BitwiseXorAssign),
+            Operators::LEFTSHIFTEQUAL => Some(CompoundOperator::LeftShiftAssign),
+            Operators::RIGHTSHIFTEQUAL => Some(CompoundOperator::RightShiftAssign),
+            Operators::DOUBLESTAREQUAL => Some(CompoundOperator::PowAssign),
+            Operators::DOUBLESLASHEQUAL => Some(CompoundOperator::FloorDivAssign),
             _ => None,
         }
     }
@@ -946,6 +1190,29 @@ impl Parser {
                     Operators::OR => Some(Operator::Or),
                     Operators::DOTDOT => Some(Operator::Range),
                     Operators::DOTDOTEQUAL => Some(Operator::RangeInclusive),
+                    Operators::AMPER => Some(Operator::BitwiseAnd),
+                    Operators::VBAR => Some(Operator::BitwiseOr),
+                    Operators::CIRCUMFLEX => Some(Operator::BitwiseXor),
+                    Operators::LEFTSHIFT => Some(Operator::LeftShift),
+                    Operators::RIGHTSHIFT => Some(Operator::RightShift),
+                    Operators::DOUBLESTAR => Some(Operator::Power),
+                    Operators::DOUBLESLASH => Some(Operator::FloorDivision),
+                    _ => None,
+                }
+            }
+            // operateurs en toutes lettres: and, or, in, not in, is, is not
+            TokenType::KEYWORD(Keywords::AND) => Some(Operator::And),
+            TokenType::KEYWORD(Keywords::OR) => Some(Operator::Or),
+            TokenType::KEYWORD(Keywords::IN) => Some(Operator::In),
+            TokenType::KEYWORD(Keywords::IS) => {
+                match self.peek_next_token().map(|t| &t.token_type) {
+                    Some(TokenType::KEYWORD(Keywords::NOT)) => Some(Operator::IsNot),
+                    _ => Some(Operator::Is),
+                }
+            }
+            TokenType::KEYWORD(Keywords::NOT) => {
+                match self.peek_next_token().map(|t| &t.token_type) {
+                    Some(TokenType::KEYWORD(Keywords::IN)) => Some(Operator::NotIn),
                     _ => None,
                 }
             }
@@ -1052,6 +1319,17 @@ impl Parser {
 
     }
 
+    /// Segment d'un chemin de module : un mot-cle y est un nom comme un autre (`pkg.mod`)
+    fn consume_path_segment(&mut self) -> Result<String, ParserError> {
+        let current_token = self.current_token().ok_or_else(|| ParserError::new(UnexpectedEOF,self.current_position()))?;
+        if let TokenType::KEYWORD(_) = &current_token.token_type {
+            let name = current_token.text.clone();
+            self.advance();
+            return Ok(name);
+        }
+        self.consume_identifier()
+    }
+
     /// Fonction pour afficher les tokens autour de l'erreur
     pub fn create_error_with_context(&self, error_type: ParserErrorType) -> ParserError {
         self.print_surrounding_tokens();
@@ -1113,6 +1391,17 @@ impl Parser {
         }
     }
 
+    /// verifie si on est en fin d'instruction (pour les instructions a valeur optionnelle)
+    pub fn is_statement_end(&self) -> bool {
+        self.check(&[
+            TokenType::NEWLINE,
+            TokenType::DEDENT,
+            TokenType::EOF,
+            TokenType::DELIMITER(Delimiters::SEMICOLON),
+            TokenType::DELIMITER(Delimiters::RCURBRACE),
+        ]) || self.is_at_end()
+    }
+
     /// fonction pour verifier la sequence de tokens a utiliser plus tard
     pub fn check_sequence(&self, tokens: &[TokenType]) -> bool {
         for (i, token_type) in tokens.iter().enumerate() {
@@ -95,15 +95,11 @@ pub enum ParserErrorType {
     //TypeInferenceNotSupported,/
     TypeInferenceError,
 
-    MissingExceptHandler
-
-
-
-
-
-
-
-
+    MissingExceptHandler,
+    MissingElseBranch,
+    InvalidSlice,
+    PositionalArgumentAfterKeyword,
+    InvalidParameterOrder,
 
 }
 
@@ -213,6 +209,10 @@ impl Display for ParserErrorType {
 
             ParserErrorType::TypeInferenceError => write!(f, "TypeInferenceError"),
             ParserErrorType::MissingExceptHandler => write!(f, "MissingExceptHandler"),
+            ParserErrorType::MissingElseBranch => write!(f, "MissingElseBranch"),
+            ParserErrorType::InvalidSlice => write!(f, "InvalidSlice"),
+            ParserErrorType::PositionalArgumentAfterKeyword => write!(f, "PositionalArgumentAfterKeyword"),
+            ParserErrorType::InvalidParameterOrder => write!(f, "InvalidParameterOrder"),
 
 
 
@@ -295,6 +295,10 @@ impl ParserError {
             ParserErrorType::TypeInferenceError => "Type inference error".to_string(),
 
             ParserErrorType::MissingExceptHandler => "Missing except handler".to_string(),
+            ParserErrorType::MissingElseBranch => "Missing else branch in conditional expression".to_string(),
+            ParserErrorType::InvalidSlice => "Invalid slice: use either a[start:end:step] or a[start..end]".to_string(),
+            ParserErrorType::PositionalArgumentAfterKeyword => "Positional argument follows keyword argument".to_string(),
+            ParserErrorType::InvalidParameterOrder => "Invalid parameter order".to_string(),
 
 
 
@@ -1,4 +1,4 @@
-use crate::parser::ast::{ASTNode, BreakStatement, ContinueStatement, ElifStatement, ExceptHandler, ForStatement, IfStatement, LoopStatement, Statement, TryStatement, Visibility, WhileStatement};
+use crate::parser::ast::{ASTNode, BreakStatement, ContinueStatement, DelStatement, ElifStatement, ExceptHandler, ForStatement, IfStatement, LoopStatement, RaiseStatement, Statement, TryStatement, Visibility, WhileStatement, WithItem, WithStatement, YieldStatement};
 use crate::parser::parser_error::{ParserError, ParserErrorType};
 use crate::parser::parser::Parser;
 use crate::tok::{Delimiters, Keywords, TokenType};
@@ -10,13 +10,13 @@ impl Parser{
     pub fn parse_if_statement(&mut self) -> Result<ASTNode, ParserError> {
         println!("Début du parsing de l'instruction if");
         self.consume(TokenType::KEYWORD(Keywords::IF))?;
-        let condition = self.parse_expression(0)?;
+        let condition = self.parse_if_condition()?;
         let then_block = self.parse_block()?;
 
         let mut elif_branches = Vec::new();
         while self.check(&[TokenType::KEYWORD(Keywords::ELIF)]) {
             self.consume(TokenType::KEYWORD(Keywords::ELIF))?;
-            let elif_condition = self.parse_expression(0)?;
+            let elif_condition = self.parse_if_condition()?;
             let elif_then_block = self.parse_block()?;
             elif_branches.push(ElifStatement {
                 condition: elif_condition,
@@ -44,8 +44,8 @@ impl Parser{
 
         self.consume(TokenType::KEYWORD(Keywords::WHILE))?;
 
-        let condition = self.parse_expression(0)?;
-        let body = self.parse_body_block()?;
+        let condition = self.parse_if_condition()?;
+        let body = self.parse_block()?;
         println!("Fin du parsing de l'instruction while OK!!!!!!!!!!!!!!");
         Ok(ASTNode::Statement(Statement::WhileStatement(WhileStatement{
             condition,
@@ -76,7 +76,7 @@ impl Parser{
 
         let iterator = self.consume_identifier()?;
         self.consume(TokenType::KEYWORD(Keywords::IN))?;
-        let iterable = self.parse_expression(0)?;
+        let iterable = self.parse_condition_expression()?;
         let body = self.parse_body_block()?;
         println!("Fin du parsing de l'instruction for OK!!!!!!!!!!!!!!!");
         Ok(ASTNode::Statement(Statement::ForStatement(ForStatement{
@@ -156,7 +156,7 @@ impl Parser{
         self.consume(TokenType::KEYWORD(Keywords::EXCEPT))?;
 
         let exception_type = if !self.check(&[TokenType::DELIMITER(Delimiters::COLON)]) {
-            Some(self.parse_expression(0)?)
+            Some(self.parse_expression_without_cast()?)
         } else {
             None
         };
@@ -180,6 +180,101 @@ impl Parser{
     }
 
 
+    /// fonction pour le parsing de with a as x, b as y
+    pub fn parse_with_statement(&mut self) -> Result<ASTNode, ParserError> {
+        println!("Début du parsing de l'instruction with");
+        self.consume(TokenType::KEYWORD(Keywords::WITH))?;
+
+        let mut items = Vec::new();
+        loop {
+            let context = self.parse_expression_without_cast()?;
+            let alias = if self.match_token(&[TokenType::KEYWORD(Keywords::AS)]) {
+                Some(self.consume_identifier()?)
+            } else {
+                None
+            };
+            items.push(WithItem { context, alias });
+
+            if !self.match_token(&[TokenType::DELIMITER(Delimiters::COMMA)]) {
+                break;
+            }
+        }
+
+        let body = self.parse_block()?;
+        println!("Fin du parsing de l'instruction with OK!!!!!!!!!!!!!!");
+        Ok(ASTNode::Statement(Statement::WithStatement(WithStatement {
+            items,
+            body,
+        })))
+    }
+
+    /// fonction pour le parsing de raise, raise Err et raise Err from cause
+    pub fn parse_raise_statement(&mut self) -> Result<ASTNode, ParserError> {
+        println!("Début du parsing de l'instruction raise");
+        self.consume(TokenType::KEYWORD(Keywords::RAISE))?;
+
+        let exception = if self.is_statement_end() {
+            None
+        } else {
+            Some(self.parse_expression(0)?)
+        };
+
+        let cause = if exception.is_some() && self.match_token(&[TokenType::KEYWORD(Keywords::FROM)]) {
+            Some(self.parse_expression(0)?)
+        } else {
+            None
+        };
+
+        self.consume_seperator();
+        println!("Fin du parsing de l'instruction raise OK!!!!!!!!!!!!!!");
+        Ok(ASTNode::Statement(Statement::RaiseStatement(RaiseStatement {
+            exception,
+            cause,
+        })))
+    }
+
+    /// fonction pour le parsing de del a, b[0], c.d
+    pub fn parse_del_statement(&mut self) -> Result<ASTNode, ParserError> {
+        println!("Début du parsing de l'instruction del");
+        self.consume(TokenType::KEYWORD(Keywords::DEL))?;
+
+        let mut targets = Vec::new();
+        loop {
+            targets.push(self.parse_expression(0)?);
+            if !self.match_token(&[TokenType::DELIMITER(Delimiters::COMMA)]) {
+                break;
+            }
+        }
+
+        self.consume_seperator();
+        println!("Fin du parsing de l'instruction del OK!!!!!!!!!!!!!!");
+        Ok(ASTNode::Statement(Statement::DelStatement(DelStatement {
+            targets,
+        })))
+    }
+
+    /// fonction pour le parsing de yield et yield valeur
+    pub fn parse_yield_statement(&mut self) -> Result<ASTNode, ParserError> {
+        println!("Début du parsing de l'instruction yield");
+        self.consume(TokenType::KEYWORD(Keywords::YIELD))?;
+
+        // yield from iterable : l'iterable est obligatoire
+        let from = self.match_token(&[TokenType::KEYWORD(Keywords::FROM)]);
+        let value = if self.is_statement_end() && !from {
+            None
+        } else {
+            Some(self.parse_expression(0)?)
+        };
+
+        self.consume_seperator();
+        println!("Fin du parsing de l'instruction yield OK!!!!!!!!!!!!!!");
+        Ok(ASTNode::Statement(Statement::YieldStatement(YieldStatement {
+            value,
+            from,
+        })))
+    }
+
+
     ///fonction principal pour  le parsing des statements
 
     pub fn parse_statement(&mut self) -> Result<ASTNode, ParserError> {
@@ -213,7 +308,8 @@ impl Parser{
 
         if self.check(&[TokenType::KEYWORD(Keywords::LET)]){
             self.parse_variable_declaration()
-        }else if self.check(&[TokenType::KEYWORD(Keywords::FN)]) {
+        }else if self.check(&[TokenType::KEYWORD(Keywords::FN)])
+            || self.check_sequence(&[TokenType::KEYWORD(Keywords::ASYNC), TokenType::KEYWORD(Keywords::FN)]) {
             let visibility = visibility.unwrap_or(Visibility::Private);
             self.parse_function_declaration(visibility)
         }else if self.check(&[TokenType::KEYWORD(Keywords::CONST)]){
@@ -234,10 +330,15 @@ impl Parser{
         }else if self.check(&[TokenType::KEYWORD(Keywords::IMPL)]) {
             let visibility = visibility.unwrap_or(Visibility::Private);
             self.parse_impl_declaration(visibility)
+        }else if self.check(&[TokenType::KEYWORD(Keywords::MOD)]) {
+            let visibility = visibility.unwrap_or(Visibility::Private);
+            self.parse_module_declaration(visibility)
         }else if self.check(&[TokenType::KEYWORD(Keywords::LOOP)]){
             self.parse_loop_statement()
         }else if self.match_token(&[TokenType::KEYWORD(Keywords::IMPORT),TokenType::KEYWORD(Keywords::USE)]){
             self.parse_module_import_statement()
+        }else if self.match_token(&[TokenType::KEYWORD(Keywords::FROM)]){
+            self.parse_from_import_statement()
         }else if self.check(&[TokenType::KEYWORD(Keywords::RETURN)]) {
             self.parse_return_statement()
         }else if self.check(&[TokenType::KEYWORD(Keywords::IF)]){
@@ -265,6 +366,14 @@ impl Parser{
             self.parse_match_statement()
         }else if self.check(&[TokenType::KEYWORD(Keywords::TRY)]) {
             self.parse_try_statement()
+        }else if self.check(&[TokenType::KEYWORD(Keywords::WITH)]) {
+            self.parse_with_statement()
+        }else if self.check(&[TokenType::KEYWORD(Keywords::RAISE)]) {
+            self.parse_raise_statement()
+        }else if self.check(&[TokenType::KEYWORD(Keywords::DEL)]) {
+            self.parse_del_statement()
+        }else if self.check(&[TokenType::KEYWORD(Keywords::YIELD)]) {
+            self.parse_yield_statement()
 
             // }else if self.check(&[TokenType::KEYWORD(Keywords::WHERE)]){
             //     self.parse_where_clauses()
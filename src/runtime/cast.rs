@@ -0,0 +1,99 @@
+//! Conversion des valeurs par `expr as Type` (voir `semantic::cast` pour les casts autorises) :
+//! - entier -> entier : troncature modulo 2^bits (complement a deux)
+//! - flottant -> entier : arrondi vers zero puis saturation aux bornes, NaN donne 0
+//! - entier -> flottant, flottant -> flottant : arrondi au plus proche
+//! - bool -> entier : 0 ou 1, char -> entier : point de code puis troncature
+//! - u8 -> char : seul cast entier -> char autorise
+
+use num_bigint::BigInt;
+use num_traits::{ToPrimitive, Zero};
+
+use crate::semantic::cast::ScalarType;
+
+/// Valeur scalaire manipulee par les conversions
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScalarValue {
+    Int(BigInt),
+    Float(f64),
+    Char(char),
+    Bool(bool),
+}
+
+/// Conversion d'une valeur scalaire vers le type cible
+pub fn cast_value(value: &ScalarValue, target: ScalarType) -> Result<ScalarValue, String> {
+    match (value, target) {
+        (ScalarValue::Int(v), ScalarType::Int { bits, signed }) => {
+            Ok(ScalarValue::Int(wrap_integer(v, bits, signed)))
+        }
+        (ScalarValue::Int(v), ScalarType::Float { bits }) => {
+            let float = v.to_f64().unwrap_or(if v.sign() == num_bigint::Sign::Minus {
+                f64::NEG_INFINITY
+            } else {
+                f64::INFINITY
+            });
+            Ok(ScalarValue::Float(round_float(float, bits)))
+        }
+        (ScalarValue::Float(v), ScalarType::Int { bits, signed }) => {
+            Ok(ScalarValue::Int(saturate_float(*v, bits, signed)))
+        }
+        (ScalarValue::Float(v), ScalarType::Float { bits }) => {
+            Ok(ScalarValue::Float(round_float(*v, bits)))
+        }
+        (ScalarValue::Bool(b), ScalarType::Int { .. }) => {
+            Ok(ScalarValue::Int(BigInt::from(*b as u8)))
+        }
+        (ScalarValue::Char(c), ScalarType::Int { bits, signed }) => {
+            Ok(ScalarValue::Int(wrap_integer(&BigInt::from(*c as u32), bits, signed)))
+        }
+        (ScalarValue::Int(v), ScalarType::Char) => match v.to_u8() {
+            Some(byte) => Ok(ScalarValue::Char(byte as char)),
+            None => Err(format!("Only u8 can be cast as char, found {}", v)),
+        },
+        (ScalarValue::Char(c), ScalarType::Char) => Ok(ScalarValue::Char(*c)),
+        (ScalarValue::Bool(b), ScalarType::Bool) => Ok(ScalarValue::Bool(*b)),
+        _ => Err(format!("Invalid cast: {:?} as {:?}", value, target)),
+    }
+}
+
+/// Troncature modulo 2^bits (complement a deux pour les entiers signes)
+fn wrap_integer(value: &BigInt, bits: u32, signed: bool) -> BigInt {
+    let modulus = BigInt::from(1) << bits;
+    let mut wrapped = value % &modulus;
+    if wrapped < BigInt::zero() {
+        wrapped += &modulus;
+    }
+    if signed && wrapped >= (BigInt::from(1) << (bits - 1)) {
+        wrapped -= modulus;
+    }
+    wrapped
+}
+
+/// Arrondi vers zero puis saturation aux bornes du type entier, NaN donne 0
+fn saturate_float(value: f64, bits: u32, signed: bool) -> BigInt {
+    let (min, max) = if signed {
+        (
+            -(BigInt::from(1) << (bits - 1)),
+            (BigInt::from(1) << (bits - 1)) - 1,
+        )
+    } else {
+        (BigInt::zero(), (BigInt::from(1) << bits) - 1)
+    };
+
+    // `as i128` arrondi vers zero, sature et donne 0 pour NaN ; toutes les largeurs tiennent dans un i128
+    let truncated = BigInt::from(value as i128);
+    if truncated < min {
+        min
+    } else if truncated > max {
+        max
+    } else {
+        truncated
+    }
+}
+
+fn round_float(value: f64, bits: u32) -> f64 {
+    if bits == 32 {
+        value as f32 as f64
+    } else {
+        value
+    }
+}
@@ -0,0 +1,139 @@
+//! Valeurs de fermeture : parametres, corps et environnement capture.
+//!
+//! Une fermeture est construite a partir de l'environnement ou elle est evaluee, selon
+//! les captures calculees par `semantic::closures` :
+//! - une capture par reference ou par reference mutable partage la liaison de
+//!   l'environnement englobant ; seule la seconde peut l'affecter
+//! - une capture `move` copie la valeur dans une liaison propre a la fermeture
+//!
+//! Les liaisons etant partagees par comptage de references, une fermeture peut etre
+//! stockee, passee en argument ou renvoyee et survivre a la portee qui l'a creee.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::parser::ast::{CaptureMode, LambdaExpression};
+
+/// Emplacement d'une variable, partage entre une portee et les fermetures qui la capturent
+pub type Slot<T> = Rc<RefCell<T>>;
+
+struct Binding<T> {
+    slot: Slot<T>,
+    mutable: bool,
+}
+
+/// Variables visibles depuis une portee
+pub struct Environment<T> {
+    bindings: HashMap<String, Binding<T>>,
+}
+
+impl<T> Default for Environment<T> {
+    fn default() -> Self {
+        Environment { bindings: HashMap::new() }
+    }
+}
+
+impl<T> Environment<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare (ou masque) une variable affectable
+    pub fn define(&mut self, name: &str, value: T) {
+        self.bind(name, Rc::new(RefCell::new(value)), true);
+    }
+
+    fn bind(&mut self, name: &str, slot: Slot<T>, mutable: bool) {
+        self.bindings.insert(name.to_string(), Binding { slot, mutable });
+    }
+
+    pub fn slot(&self, name: &str) -> Option<Slot<T>> {
+        self.bindings.get(name).map(|binding| binding.slot.clone())
+    }
+
+    pub fn get(&self, name: &str) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.bindings.get(name).map(|binding| binding.slot.borrow().clone())
+    }
+
+    pub fn set(&mut self, name: &str, value: T) -> Result<(), String> {
+        match self.bindings.get(name) {
+            Some(binding) if binding.mutable => {
+                *binding.slot.borrow_mut() = value;
+                Ok(())
+            }
+            Some(_) => Err(format!("Cannot assign to '{}': it is captured by reference", name)),
+            None => Err(format!("Undefined variable '{}'", name)),
+        }
+    }
+}
+
+type Body<T> = dyn Fn(&mut Environment<T>) -> Result<T, String>;
+
+/// Fermeture evaluee : le corps s'execute dans un environnement forme des captures et des arguments
+pub struct Closure<T> {
+    parameters: Vec<String>,
+    captures: Vec<(String, CaptureMode, Slot<T>)>,
+    body: Rc<Body<T>>,
+}
+
+impl<T> Clone for Closure<T> {
+    fn clone(&self) -> Self {
+        Closure {
+            parameters: self.parameters.clone(),
+            captures: self.captures.clone(),
+            body: self.body.clone(),
+        }
+    }
+}
+
+impl<T: Clone> Closure<T> {
+    /// Evalue `lambda` dans `environment` ; ses captures doivent deja avoir ete resolues
+    pub fn capture(
+        lambda: &LambdaExpression,
+        environment: &Environment<T>,
+        body: impl Fn(&mut Environment<T>) -> Result<T, String> + 'static,
+    ) -> Result<Self, String> {
+        let mut captures = Vec::new();
+        for capture in &lambda.captures {
+            let slot = environment
+                .slot(&capture.name)
+                .ok_or_else(|| format!("Captured variable '{}' is not defined", capture.name))?;
+            let slot = match capture.mode {
+                CaptureMode::Move => Rc::new(RefCell::new(slot.borrow().clone())),
+                CaptureMode::Reference | CaptureMode::MutableReference => slot,
+            };
+            captures.push((capture.name.clone(), capture.mode, slot));
+        }
+        Ok(Closure {
+            parameters: lambda.parameters.iter().map(|parameter| parameter.name.clone()).collect(),
+            captures,
+            body: Rc::new(body),
+        })
+    }
+
+    pub fn arity(&self) -> usize {
+        self.parameters.len()
+    }
+
+    pub fn call(&self, arguments: Vec<T>) -> Result<T, String> {
+        if arguments.len() != self.parameters.len() {
+            return Err(format!(
+                "Closure expects {} arguments, got {}",
+                self.parameters.len(),
+                arguments.len()
+            ));
+        }
+        let mut frame = Environment::new();
+        for (name, mode, slot) in &self.captures {
+            frame.bind(name, slot.clone(), *mode != CaptureMode::Reference);
+        }
+        for (name, value) in self.parameters.iter().zip(arguments) {
+            frame.define(name, value);
+        }
+        (self.body)(&mut frame)
+    }
+}
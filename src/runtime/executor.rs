@@ -0,0 +1,244 @@
+//! Boucle d'evenements mono-thread et deterministe pour les fonctions `async`.
+//!
+//! - Les taches pretes s'executent dans l'ordre FIFO de leur reveil.
+//! - Le temps est virtuel : quand plus aucune tache n'est prete, l'horloge saute
+//!   directement a la prochaine echeance de timer. Les timers de meme echeance
+//!   se declenchent dans leur ordre de creation.
+//!
+//! Deux executions du meme programme donnent donc toujours le meme ordonnancement,
+//! sans dependre de l'horloge systeme.
+
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::time::Duration;
+
+type TaskId = usize;
+type Task = Pin<Box<dyn Future<Output = ()>>>;
+
+/// File des taches reveillees, partagee avec les wakers
+#[derive(Default)]
+struct ReadyQueue(Mutex<VecDeque<TaskId>>);
+
+struct TaskWaker {
+    id: TaskId,
+    queue: Arc<ReadyQueue>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.queue.0.lock().unwrap().push_back(self.id);
+    }
+}
+
+#[derive(Default)]
+struct State {
+    now: Duration,
+    next_task: TaskId,
+    next_timer: u64,
+    tasks: HashMap<TaskId, Task>,
+    // (echeance, ordre de creation) -> waker de la tache endormie
+    timers: BinaryHeap<Reverse<(Duration, u64)>>,
+    timer_wakers: HashMap<u64, Waker>,
+}
+
+/// Acces a la boucle depuis les taches : lancer une tache, dormir, lire l'horloge
+#[derive(Clone)]
+pub struct Handle {
+    state: Rc<RefCell<State>>,
+    ready: Arc<ReadyQueue>,
+}
+
+#[derive(Default)]
+pub struct Executor {
+    handle: Handle,
+}
+
+impl Default for Handle {
+    fn default() -> Self {
+        Handle {
+            state: Rc::new(RefCell::new(State::default())),
+            ready: Arc::new(ReadyQueue::default()),
+        }
+    }
+}
+
+impl Executor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn handle(&self) -> Handle {
+        self.handle.clone()
+    }
+
+    /// Execute `future` et toutes les taches lancees jusqu'a ce qu'il se termine.
+    /// Erreur si plus aucune tache ne peut progresser (interblocage).
+    pub fn block_on<F>(&self, future: F) -> Result<F::Output, String>
+    where
+        F: Future + 'static,
+        F::Output: 'static,
+    {
+        let mut main = self.handle.spawn(future);
+
+        loop {
+            self.run_ready_tasks();
+
+            if let Some(output) = main.take_output() {
+                return Ok(output);
+            }
+            if !self.fire_next_timers() {
+                return Err("Deadlock: no task can make progress".to_string());
+            }
+        }
+    }
+
+    fn run_ready_tasks(&self) {
+        loop {
+            let next = self.handle.ready.0.lock().unwrap().pop_front();
+            let Some(id) = next else { break };
+
+            // la tache est sortie de la table pendant le poll : elle peut en lancer d'autres
+            let task = self.handle.state.borrow_mut().tasks.remove(&id);
+            let Some(mut task) = task else { continue };
+
+            let waker = Waker::from(Arc::new(TaskWaker {
+                id,
+                queue: self.handle.ready.clone(),
+            }));
+            if task.as_mut().poll(&mut Context::from_waker(&waker)).is_pending() {
+                self.handle.state.borrow_mut().tasks.insert(id, task);
+            }
+        }
+    }
+
+    /// Avance l'horloge a la prochaine echeance et reveille les timers concernes
+    fn fire_next_timers(&self) -> bool {
+        let mut state = self.handle.state.borrow_mut();
+        let Some(Reverse((deadline, _))) = state.timers.peek().copied() else {
+            return false;
+        };
+        state.now = state.now.max(deadline);
+
+        while let Some(Reverse((next_deadline, timer))) = state.timers.peek().copied() {
+            if next_deadline > deadline {
+                break;
+            }
+            state.timers.pop();
+            if let Some(waker) = state.timer_wakers.remove(&timer) {
+                waker.wake();
+            }
+        }
+        true
+    }
+}
+
+impl Handle {
+    /// Lance une tache ; le JoinHandle renvoye est lui-meme un Future de son resultat
+    pub fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + 'static,
+        F::Output: 'static,
+    {
+        let slot = Rc::new(RefCell::new(JoinSlot {
+            output: None,
+            waiter: None,
+        }));
+        let task_slot = slot.clone();
+        let task: Task = Box::pin(async move {
+            let output = future.await;
+            let mut slot = task_slot.borrow_mut();
+            slot.output = Some(output);
+            if let Some(waiter) = slot.waiter.take() {
+                waiter.wake();
+            }
+        });
+
+        let mut state = self.state.borrow_mut();
+        let id = state.next_task;
+        state.next_task += 1;
+        state.tasks.insert(id, task);
+        self.ready.0.lock().unwrap().push_back(id);
+
+        JoinHandle { slot }
+    }
+
+    /// Future qui se termine apres `duration` de temps virtuel
+    pub fn sleep(&self, duration: Duration) -> Sleep {
+        Sleep {
+            handle: self.clone(),
+            deadline: self.now() + duration,
+            registered: false,
+        }
+    }
+
+    /// Temps virtuel ecoule depuis le demarrage de la boucle
+    pub fn now(&self) -> Duration {
+        self.state.borrow().now
+    }
+}
+
+struct JoinSlot<T> {
+    output: Option<T>,
+    waiter: Option<Waker>,
+}
+
+pub struct JoinHandle<T> {
+    slot: Rc<RefCell<JoinSlot<T>>>,
+}
+
+impl<T> JoinHandle<T> {
+    fn take_output(&mut self) -> Option<T> {
+        self.slot.borrow_mut().output.take()
+    }
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<T> {
+        let mut slot = self.slot.borrow_mut();
+        match slot.output.take() {
+            Some(output) => Poll::Ready(output),
+            None => {
+                slot.waiter = Some(context.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+pub struct Sleep {
+    handle: Handle,
+    deadline: Duration,
+    registered: bool,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<()> {
+        if self.handle.now() >= self.deadline {
+            return Poll::Ready(());
+        }
+        if !self.registered {
+            let mut state = self.handle.state.borrow_mut();
+            let timer = state.next_timer;
+            state.next_timer += 1;
+            state.timers.push(Reverse((self.deadline, timer)));
+            state.timer_wakers.insert(timer, context.waker().clone());
+            drop(state);
+            self.registered = true;
+        }
+        Poll::Pending
+    }
+}
@@ -0,0 +1,67 @@
+//! Generateurs executes comme des frames reprenables.
+//!
+//! Chaque `resume` execute le corps jusqu'au prochain `yield` puis rend la main.
+//! `yield from` delegue a un autre iterateur jusqu'a son epuisement avant de
+//! reprendre la frame. Un `Generator` implemente `Iterator`, il s'utilise donc
+//! directement dans un `for` ou une comprehension.
+
+/// Etat rendu par une frame a chaque reprise
+pub enum Resume<T> {
+    Yield(T),
+    YieldFrom(Box<dyn Iterator<Item = T>>),
+    Return,
+}
+
+/// Corps d'un generateur, repris a chaque `next`
+pub trait Frame<T> {
+    fn resume(&mut self) -> Resume<T>;
+}
+
+impl<T, F: FnMut() -> Resume<T>> Frame<T> for F {
+    fn resume(&mut self) -> Resume<T> {
+        self()
+    }
+}
+
+pub struct Generator<T> {
+    frame: Option<Box<dyn Frame<T>>>,
+    delegate: Option<Box<dyn Iterator<Item = T>>>,
+}
+
+impl<T> Generator<T> {
+    pub fn new(frame: impl Frame<T> + 'static) -> Self {
+        Generator {
+            frame: Some(Box::new(frame)),
+            delegate: None,
+        }
+    }
+
+    /// Vrai une fois que le corps a atteint sa fin (ou un `return`)
+    pub fn is_finished(&self) -> bool {
+        self.frame.is_none() && self.delegate.is_none()
+    }
+}
+
+impl<T> Iterator for Generator<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            if let Some(delegate) = self.delegate.as_mut() {
+                match delegate.next() {
+                    Some(value) => return Some(value),
+                    None => self.delegate = None,
+                }
+            }
+
+            match self.frame.as_mut()?.resume() {
+                Resume::Yield(value) => return Some(value),
+                Resume::YieldFrom(delegate) => self.delegate = Some(delegate),
+                Resume::Return => {
+                    self.frame = None;
+                    return None;
+                }
+            }
+        }
+    }
+}
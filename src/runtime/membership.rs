@@ -0,0 +1,99 @@
+//! Evaluation des tests d'appartenance (`in`, `not in`) et d'identite (`is`, `is not`).
+//!
+//! `in` suit le protocole de containment verifie par l'inference :
+//! - tableau, liste, ensemble, intervalle : egalite avec un element
+//! - dictionnaire : presence parmi les cles
+//! - chaine : sous-chaine ou caractere
+//!
+//! `is` compare les references et non les valeurs : deux valeurs egales mais allouees
+//! separement ne sont pas identiques.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::{Range, RangeInclusive};
+use std::rc::Rc;
+
+/// Conteneur dans lequel on peut chercher un `T` avec `in`
+pub trait Contains<T: ?Sized> {
+    fn contains_value(&self, value: &T) -> bool;
+}
+
+impl<T: PartialEq> Contains<T> for [T] {
+    fn contains_value(&self, value: &T) -> bool {
+        self.contains(value)
+    }
+}
+
+impl<T: PartialEq> Contains<T> for Vec<T> {
+    fn contains_value(&self, value: &T) -> bool {
+        self.contains(value)
+    }
+}
+
+impl<T: Eq + Hash> Contains<T> for HashSet<T> {
+    fn contains_value(&self, value: &T) -> bool {
+        self.contains(value)
+    }
+}
+
+impl<T: Ord> Contains<T> for BTreeSet<T> {
+    fn contains_value(&self, value: &T) -> bool {
+        self.contains(value)
+    }
+}
+
+impl<K: Eq + Hash, V> Contains<K> for HashMap<K, V> {
+    fn contains_value(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+}
+
+impl<K: Ord, V> Contains<K> for BTreeMap<K, V> {
+    fn contains_value(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+}
+
+impl<T: PartialOrd> Contains<T> for Range<T> {
+    fn contains_value(&self, value: &T) -> bool {
+        self.contains(value)
+    }
+}
+
+impl<T: PartialOrd> Contains<T> for RangeInclusive<T> {
+    fn contains_value(&self, value: &T) -> bool {
+        self.contains(value)
+    }
+}
+
+impl Contains<str> for str {
+    fn contains_value(&self, value: &str) -> bool {
+        self.contains(value)
+    }
+}
+
+impl Contains<char> for str {
+    fn contains_value(&self, value: &char) -> bool {
+        self.contains(*value)
+    }
+}
+
+/// `value in container`
+pub fn is_in<T: ?Sized, C: Contains<T> + ?Sized>(value: &T, container: &C) -> bool {
+    container.contains_value(value)
+}
+
+/// `value not in container`
+pub fn not_in<T: ?Sized, C: Contains<T> + ?Sized>(value: &T, container: &C) -> bool {
+    !is_in(value, container)
+}
+
+/// `left is right` : meme allocation
+pub fn is<T: ?Sized>(left: &Rc<T>, right: &Rc<T>) -> bool {
+    Rc::ptr_eq(left, right)
+}
+
+/// `left is not right`
+pub fn is_not<T: ?Sized>(left: &Rc<T>, right: &Rc<T>) -> bool {
+    !is(left, right)
+}
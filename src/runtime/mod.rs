@@ -0,0 +1,7 @@
+pub mod cast;
+pub mod closure;
+pub mod executor;
+pub mod generator;
+pub mod membership;
+pub mod slice;
+pub mod try_op;
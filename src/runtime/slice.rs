@@ -0,0 +1,103 @@
+//! Semantique des tranches `a[debut:fin:pas]` et `a[debut..fin]`, a la Python :
+//!
+//! - une borne negative compte depuis la fin (`a[-2:]` : les deux derniers elements)
+//! - une borne hors limites est ramenee dans les limites, sans erreur
+//! - un pas negatif parcourt a l'envers ; `a[::-1]` renverse, les bornes absentes
+//!   designant alors la fin puis le debut
+//! - `a[debut..=fin]` inclut `fin`
+//! - un pas nul est une erreur
+//!
+//! Une tranche de pas 1 est une vue qui emprunte la sequence d'origine (`Cow::Borrowed`) ;
+//! toute autre tranche est une copie (`Cow::Owned`).
+
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SliceBounds {
+    pub start: Option<i64>,
+    pub end: Option<i64>,
+    pub step: Option<i64>,
+    pub inclusive: bool,
+}
+
+impl SliceBounds {
+    pub fn new(start: Option<i64>, end: Option<i64>, step: Option<i64>) -> Self {
+        SliceBounds { start, end, step, inclusive: false }
+    }
+
+    /// `debut..=fin`
+    pub fn inclusive_range(start: Option<i64>, end: i64) -> Self {
+        SliceBounds { start, end: Some(end), step: None, inclusive: true }
+    }
+
+    /// Positions selectionnees dans une sequence de longueur `len`, dans l'ordre de parcours
+    pub fn indices(&self, len: usize) -> Result<Vec<usize>, String> {
+        let (start, end, step) = self.normalize(len)?;
+        let mut indices = Vec::new();
+        let mut index = start;
+        while (step > 0 && index < end) || (step < 0 && index > end) {
+            indices.push(index as usize);
+            index += step;
+        }
+        Ok(indices)
+    }
+
+    /// Debut, fin exclue et pas, ramenes dans les limites ; une fin de -1 signifie
+    /// "apres le premier element" pour un pas negatif
+    fn normalize(&self, len: usize) -> Result<(i64, i64, i64), String> {
+        let len = len as i64;
+        let step = self.step.unwrap_or(1);
+        if step == 0 {
+            return Err("Slice step cannot be zero".to_string());
+        }
+        let clamp = |index: i64| {
+            if index < 0 {
+                if step < 0 { -1 } else { 0 }
+            } else if index >= len {
+                if step < 0 { len - 1 } else { len }
+            } else {
+                index
+            }
+        };
+        let from_end = |index: i64| if index < 0 { index + len } else { index };
+
+        let start = match self.start {
+            Some(start) => clamp(from_end(start)),
+            None if step < 0 => len - 1,
+            None => 0,
+        };
+        let end = match self.end {
+            Some(end) if self.inclusive => clamp(from_end(end) + step.signum()),
+            Some(end) => clamp(from_end(end)),
+            None if step < 0 => -1,
+            None => len,
+        };
+        Ok((start, end, step))
+    }
+}
+
+/// Tranche d'un tableau : vue si le pas vaut 1, copie sinon
+pub fn slice<'a, T: Clone>(items: &'a [T], bounds: &SliceBounds) -> Result<Cow<'a, [T]>, String> {
+    let (start, end, step) = bounds.normalize(items.len())?;
+    if step == 1 {
+        let start = start as usize;
+        return Ok(Cow::Borrowed(&items[start..(end as usize).max(start)]));
+    }
+    let indices = bounds.indices(items.len())?;
+    Ok(Cow::Owned(indices.into_iter().map(|index| items[index].clone()).collect()))
+}
+
+/// Tranche d'une chaine, comptee en caracteres : vue si le pas vaut 1, copie sinon
+pub fn slice_str<'a>(text: &'a str, bounds: &SliceBounds) -> Result<Cow<'a, str>, String> {
+    let boundaries: Vec<usize> = text.char_indices().map(|(offset, _)| offset).chain([text.len()]).collect();
+    let len = boundaries.len() - 1;
+    let (start, end, step) = bounds.normalize(len)?;
+    if step == 1 {
+        let start = start as usize;
+        let end = (end as usize).max(start);
+        return Ok(Cow::Borrowed(&text[boundaries[start]..boundaries[end]]));
+    }
+    let chars: Vec<char> = text.chars().collect();
+    let indices = bounds.indices(len)?;
+    Ok(Cow::Owned(indices.into_iter().map(|index| chars[index]).collect()))
+}
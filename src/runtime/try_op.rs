@@ -0,0 +1,64 @@
+//! Execution de l'operateur `?` (voir `semantic::builtins` pour son typage).
+//!
+//! `branch` rend la valeur portee par `Some`/`Ok`, ou la valeur que la fonction
+//! englobante doit renvoyer immediatement : `None` ou `Err(e)`.
+//!
+//! ```text
+//! let value = match branch(operand) {
+//!     Ok(value) => value,
+//!     Err(early_return) => return early_return,
+//! };
+//! ```
+
+use std::ops::ControlFlow;
+
+/// Type accepte par `?` : `Option<T>` ou `Result<T, E>`
+pub trait Try: Sized {
+    type Output;
+    /// Ce qui est propage hors de la fonction : rien pour `None`, l'erreur pour `Err`
+    type Residual;
+
+    fn split(self) -> ControlFlow<Self::Residual, Self::Output>;
+    fn from_residual(residual: Self::Residual) -> Self;
+}
+
+impl<T> Try for Option<T> {
+    type Output = T;
+    type Residual = ();
+
+    fn split(self) -> ControlFlow<(), T> {
+        match self {
+            Some(value) => ControlFlow::Continue(value),
+            None => ControlFlow::Break(()),
+        }
+    }
+
+    fn from_residual(_: ()) -> Self {
+        None
+    }
+}
+
+impl<T, E> Try for Result<T, E> {
+    type Output = T;
+    type Residual = E;
+
+    fn split(self) -> ControlFlow<E, T> {
+        match self {
+            Ok(value) => ControlFlow::Continue(value),
+            Err(error) => ControlFlow::Break(error),
+        }
+    }
+
+    fn from_residual(error: E) -> Self {
+        Err(error)
+    }
+}
+
+/// `operand?` dans une fonction renvoyant `R` : `Ok(valeur)` pour continuer,
+/// `Err(retour)` pour sortir de la fonction avec `retour`
+pub fn branch<T: Try, R: Try<Residual = T::Residual>>(operand: T) -> Result<T::Output, R> {
+    match operand.split() {
+        ControlFlow::Continue(value) => Ok(value),
+        ControlFlow::Break(residual) => Err(R::from_residual(residual)),
+    }
+}
@@ -0,0 +1,124 @@
+//! Liaison des arguments d'un appel aux parametres de la fonction, a la Python.
+//!
+//! `fn connect(host: str, port: int = 80, *rest: str, **options: bool)` :
+//! - les arguments positionnels remplissent les parametres ordinaires dans l'ordre,
+//!   le surplus va dans `*rest`
+//! - un argument nomme `port=8080` remplit le parametre de ce nom, sinon va dans `**options`
+//! - un parametre non fourni prend sa valeur par defaut
+//!
+//! Un parametre ordinaire declare apres `*rest` ne peut etre fourni que par son nom.
+
+use crate::parser::ast::{Expression, GenericType, KeywordArgument, Parameter, ParameterKind, Type};
+
+/// Ce qu'il faut savoir d'un parametre pour lier un appel
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterSpec {
+    pub name: String,
+    pub parameter_type: Type,
+    pub kind: ParameterKind,
+    pub has_default: bool,
+}
+
+impl From<&Parameter> for ParameterSpec {
+    fn from(parameter: &Parameter) -> Self {
+        ParameterSpec {
+            name: parameter.name.clone(),
+            parameter_type: parameter.parameter_type.clone(),
+            kind: parameter.kind,
+            has_default: parameter.default_value.is_some(),
+        }
+    }
+}
+
+/// Valeur recue par un parametre
+#[derive(Debug, Clone)]
+pub enum Binding<'a> {
+    Argument(&'a Expression),
+    Default,
+    Variadic(Vec<&'a Expression>),
+    KeywordVariadic(Vec<&'a KeywordArgument>),
+}
+
+/// Type de la variable locale d'un parametre : `*args: T` est un `Array<T>`,
+/// `**kwargs: T` un `Dict<str, T>`
+pub fn local_type(parameter: &Parameter) -> Type {
+    match parameter.kind {
+        ParameterKind::Regular => parameter.parameter_type.clone(),
+        ParameterKind::Variadic => Type::Array(Box::new(parameter.parameter_type.clone())),
+        ParameterKind::KeywordVariadic => Type::Generic(GenericType {
+            base: "Dict".to_string(),
+            type_parameters: vec![Type::String, parameter.parameter_type.clone()],
+        }),
+    }
+}
+
+/// Lie les arguments de l'appel `function(...)` ; le resultat suit l'ordre des parametres
+pub fn bind_arguments<'a>(
+    function: &str,
+    parameters: &[ParameterSpec],
+    arguments: &'a [Expression],
+    keyword_arguments: &'a [KeywordArgument],
+) -> Result<Vec<Binding<'a>>, String> {
+    let mut bindings: Vec<Option<Binding<'a>>> = vec![None; parameters.len()];
+
+    // positionnels : parametres ordinaires avant `*args`, puis `*args`
+    let positional = parameters
+        .iter()
+        .take_while(|parameter| parameter.kind == ParameterKind::Regular)
+        .count();
+    let variadic = parameters.iter().position(|parameter| parameter.kind == ParameterKind::Variadic);
+    for (index, argument) in arguments.iter().enumerate() {
+        match (index < positional, variadic) {
+            (true, _) => bindings[index] = Some(Binding::Argument(argument)),
+            (false, Some(slot)) => match &mut bindings[slot] {
+                Some(Binding::Variadic(values)) => values.push(argument),
+                binding => *binding = Some(Binding::Variadic(vec![argument])),
+            },
+            (false, None) => {
+                return Err(format!(
+                    "{}() takes {} positional arguments but {} were given",
+                    function,
+                    positional,
+                    arguments.len()
+                ));
+            }
+        }
+    }
+
+    let keyword_variadic = parameters.iter().position(|parameter| parameter.kind == ParameterKind::KeywordVariadic);
+    for (position, keyword) in keyword_arguments.iter().enumerate() {
+        if keyword_arguments[..position].iter().any(|previous| previous.name == keyword.name) {
+            return Err(format!("{}() got multiple values for argument '{}'", function, keyword.name));
+        }
+        let slot = parameters
+            .iter()
+            .position(|parameter| parameter.name == keyword.name && parameter.kind == ParameterKind::Regular);
+        match (slot, keyword_variadic) {
+            (Some(slot), _) if bindings[slot].is_some() => {
+                return Err(format!("{}() got multiple values for argument '{}'", function, keyword.name));
+            }
+            (Some(slot), _) => bindings[slot] = Some(Binding::Argument(&keyword.value)),
+            (None, Some(slot)) => match &mut bindings[slot] {
+                Some(Binding::KeywordVariadic(values)) => values.push(keyword),
+                binding => *binding = Some(Binding::KeywordVariadic(vec![keyword])),
+            },
+            (None, None) => {
+                return Err(format!("{}() got an unexpected keyword argument '{}'", function, keyword.name));
+            }
+        }
+    }
+
+    parameters
+        .iter()
+        .zip(bindings)
+        .map(|(parameter, binding)| match (binding, parameter.kind) {
+            (Some(binding), _) => Ok(binding),
+            (None, ParameterKind::Variadic) => Ok(Binding::Variadic(Vec::new())),
+            (None, ParameterKind::KeywordVariadic) => Ok(Binding::KeywordVariadic(Vec::new())),
+            (None, ParameterKind::Regular) if parameter.has_default => Ok(Binding::Default),
+            (None, ParameterKind::Regular) => {
+                Err(format!("{}() missing required argument '{}'", function, parameter.name))
+            }
+        })
+        .collect()
+}
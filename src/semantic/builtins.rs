@@ -0,0 +1,161 @@
+//! Types generiques integres `Option<T>`, `Result<T, E>` et `Future<T>`.
+//!
+//! `Option` et `Result` sont les seuls types acceptes par l'operateur `?` :
+//! - `Option<T>` : `Some(valeur)` donne `valeur`, `None` sort de la fonction avec `None`
+//! - `Result<T, E>` : `Ok(valeur)` donne `valeur`, `Err(e)` sort de la fonction avec `Err(e)`
+//!
+//! La fonction englobante doit donc renvoyer un `Option` (pour un operande `Option`)
+//! ou un `Result` avec le meme type d'erreur (pour un operande `Result`).
+//! La sortie anticipee est executee par `runtime::try_op`.
+//!
+//! `Future<T>` est le type d'un appel a une `async fn` renvoyant `T` ;
+//! `await` sur un `Future<T>` donne `T`.
+//!
+//! `Iterator<T>` est le type d'un appel a un generateur produisant des `T`.
+
+use crate::parser::ast::{GenericType, Type};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BuiltinEnum {
+    Option,
+    Result,
+}
+
+impl BuiltinEnum {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BuiltinEnum::Option => "Option",
+            BuiltinEnum::Result => "Result",
+        }
+    }
+
+    /// Variantes et nombre de valeurs portees par chacune
+    pub fn variants(&self) -> &'static [(&'static str, usize)] {
+        match self {
+            BuiltinEnum::Option => &[("Some", 1), ("None", 0)],
+            BuiltinEnum::Result => &[("Ok", 1), ("Err", 1)],
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<BuiltinEnum> {
+        match name {
+            "Option" => Some(BuiltinEnum::Option),
+            "Result" => Some(BuiltinEnum::Result),
+            _ => None,
+        }
+    }
+
+    /// Enum integree a laquelle appartient une variante (`Some` -> `Option`)
+    pub fn from_variant(variant: &str) -> Option<BuiltinEnum> {
+        [BuiltinEnum::Option, BuiltinEnum::Result]
+            .into_iter()
+            .find(|builtin| builtin.variants().iter().any(|(name, _)| *name == variant))
+    }
+}
+
+pub fn option_type(inner: Type) -> Type {
+    Type::Generic(GenericType {
+        base: BuiltinEnum::Option.name().to_string(),
+        type_parameters: vec![inner],
+    })
+}
+
+pub fn result_type(ok: Type, err: Type) -> Type {
+    Type::Generic(GenericType {
+        base: BuiltinEnum::Result.name().to_string(),
+        type_parameters: vec![ok, err],
+    })
+}
+
+pub fn future_type(output: Type) -> Type {
+    Type::Generic(GenericType {
+        base: "Future".to_string(),
+        type_parameters: vec![output],
+    })
+}
+
+pub fn iterator_type(item: Type) -> Type {
+    Type::Generic(GenericType {
+        base: "Iterator".to_string(),
+        type_parameters: vec![item],
+    })
+}
+
+/// Type produit par `await` sur `awaited`
+pub fn check_await(awaited: &Type) -> Result<Type, String> {
+    match awaited {
+        Type::Infer => Ok(Type::Infer),
+        Type::Generic(generic) if generic.base == "Future" => {
+            Ok(generic.type_parameters.first().cloned().unwrap_or(Type::Infer))
+        }
+        other => Err(format!("'await' expects a Future, found {:?}", other)),
+    }
+}
+
+/// Decompose `Option<T>` en (Option, [T]) et `Result<T, E>` en (Result, [T, E])
+fn as_builtin(ty: &Type) -> Option<(BuiltinEnum, Vec<Type>)> {
+    match ty {
+        Type::Generic(generic) => {
+            let builtin = BuiltinEnum::from_name(&generic.base)?;
+            let mut parameters = generic.type_parameters.clone();
+            let arity = match builtin {
+                BuiltinEnum::Option => 1,
+                BuiltinEnum::Result => 2,
+            };
+            parameters.resize(arity, Type::Infer);
+            Some((builtin, parameters))
+        }
+        Type::Named(name) => {
+            let builtin = BuiltinEnum::from_name(name)?;
+            Some((builtin, Vec::new()))
+        }
+        _ => None,
+    }
+}
+
+/// Verifie `expr?` et renvoie le type de la valeur extraite.
+/// `return_type` est le type de retour de la fonction englobante, None hors d'une fonction.
+/// Le type de retour doit etre declare explicitement : `?` est refuse sans `-> Option<..>` / `-> Result<..>`.
+pub fn check_try(operand: &Type, return_type: Option<&Type>) -> Result<Type, String> {
+    let return_type = return_type.ok_or_else(|| {
+        "The '?' operator can only be used in a function returning Option or Result".to_string()
+    })?;
+    if *return_type == Type::Infer {
+        return Err("The '?' operator requires the function to declare an Option or Result return type".to_string());
+    }
+    let (return_builtin, return_parameters) = as_builtin(return_type).ok_or_else(|| {
+        format!(
+            "The '?' operator can only be used in a function returning Option or Result, found {:?}",
+            return_type
+        )
+    })?;
+
+    if *operand == Type::Infer {
+        return Ok(Type::Infer);
+    }
+    let (builtin, parameters) = as_builtin(operand).ok_or_else(|| {
+        format!("The '?' operator can only be applied to Option or Result, found {:?}", operand)
+    })?;
+
+    if builtin != return_builtin {
+        return Err(format!(
+            "The '?' operator on {} cannot be used in a function returning {:?}",
+            builtin.name(),
+            return_type
+        ));
+    }
+
+    // l'erreur propagee doit correspondre a celle de la fonction
+    if builtin == BuiltinEnum::Result {
+        let error_type = parameters.get(1).unwrap_or(&Type::Infer);
+        let return_error = return_parameters.get(1).unwrap_or(&Type::Infer);
+        if *error_type != Type::Infer && *return_error != Type::Infer && error_type != return_error {
+            return Err(format!(
+                "Mismatched error type for '?': {:?} propagated in a function returning {:?}",
+                error_type, return_type
+            ));
+        }
+    }
+
+    Ok(parameters.first().cloned().unwrap_or(Type::Infer))
+}
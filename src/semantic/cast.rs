@@ -0,0 +1,82 @@
+//! Regles de typage des casts `expr as Type`.
+//!
+//! Les types scalaires sont `int` (64 bits signe), `float` (64 bits), `char`, `bool`
+//! ainsi que les largeurs explicites `i8` .. `i64`, `u8` .. `u64`, `f32` et `f64`.
+//!
+//! Casts autorises : entre types numeriques, bool -> entier, char -> entier et u8 -> char.
+//! La conversion des valeurs a l'execution est dans `runtime::cast`.
+
+use crate::parser::ast::Type;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScalarType {
+    Int { bits: u32, signed: bool },
+    Float { bits: u32 },
+    Char,
+    Bool,
+}
+
+impl ScalarType {
+    pub const INT: ScalarType = ScalarType::Int { bits: 64, signed: true };
+    pub const FLOAT: ScalarType = ScalarType::Float { bits: 64 };
+
+    /// Type scalaire correspondant a un type de l'AST, None si le type n'est pas scalaire
+    pub fn from_type(ty: &Type) -> Option<ScalarType> {
+        match ty {
+            Type::Int => Some(ScalarType::INT),
+            Type::Float => Some(ScalarType::FLOAT),
+            Type::Char => Some(ScalarType::Char),
+            Type::Bool => Some(ScalarType::Bool),
+            Type::Named(name) | Type::Custom(name) => match name.as_str() {
+                "i8" => Some(ScalarType::Int { bits: 8, signed: true }),
+                "i16" => Some(ScalarType::Int { bits: 16, signed: true }),
+                "i32" => Some(ScalarType::Int { bits: 32, signed: true }),
+                "i64" => Some(ScalarType::Int { bits: 64, signed: true }),
+                "u8" => Some(ScalarType::Int { bits: 8, signed: false }),
+                "u16" => Some(ScalarType::Int { bits: 16, signed: false }),
+                "u32" => Some(ScalarType::Int { bits: 32, signed: false }),
+                "u64" => Some(ScalarType::Int { bits: 64, signed: false }),
+                "f32" => Some(ScalarType::Float { bits: 32 }),
+                "f64" => Some(ScalarType::FLOAT),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn is_numeric(&self) -> bool {
+        matches!(self, ScalarType::Int { .. } | ScalarType::Float { .. })
+    }
+}
+
+/// Verifie qu'un cast `from as to` est autorise et renvoie le type resultat
+pub fn check_cast(from: &Type, to: &Type) -> Result<Type, String> {
+    if from == to || *from == Type::Infer {
+        return Ok(to.clone());
+    }
+
+    let (source, target) = match (ScalarType::from_type(from), ScalarType::from_type(to)) {
+        (Some(source), Some(target)) => (source, target),
+        _ => {
+            return Err(format!(
+                "Non-primitive cast: {:?} as {:?}",
+                from, to
+            ))
+        }
+    };
+
+    let allowed = match (source, target) {
+        (source, target) if source.is_numeric() && target.is_numeric() => true,
+        (ScalarType::Bool, ScalarType::Int { .. }) => true,
+        (ScalarType::Char, ScalarType::Int { .. }) => true,
+        (ScalarType::Int { bits: 8, signed: false }, ScalarType::Char) => true,
+        (ScalarType::Char, ScalarType::Char) | (ScalarType::Bool, ScalarType::Bool) => true,
+        _ => false,
+    };
+
+    if allowed {
+        Ok(to.clone())
+    } else {
+        Err(format!("Invalid cast: {:?} as {:?}", from, to))
+    }
+}
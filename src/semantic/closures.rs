@@ -0,0 +1,424 @@
+//! Analyse des captures des fermetures.
+//!
+//! Une fermeture (`|x| x + n`, `lambda(x) => x + n`) capture chaque variable de la
+//! portee englobante qu'elle utilise :
+//! - par reference si elle ne fait que la lire
+//! - par reference mutable si elle l'affecte (`n += 1`, `p.x = 0`, `&mut n`) ; la
+//!   variable doit alors etre declaree `mut`
+//! - par valeur pour une fermeture `move`
+//!
+//! Une fermeture imbriquee fait aussi capturer ses variables par la fermeture qui
+//! l'entoure. Les fonctions, constantes et autres elements de module ne sont pas captures.
+//! Une premiere affectation `x = ...` a un nom inconnu le declare, comme en mode indentation.
+
+use std::collections::HashMap;
+
+use crate::parser::ast::{
+    ASTNode, BorrowType, Capture, CaptureMode, CollectionAccess, Declaration, Expression, FunctionDeclaration,
+    LambdaExpression, Mutability, Pattern, Statement,
+};
+
+/// Calcule les captures de chaque fermeture du corps de `function`
+/// (champ `captures` de `LambdaExpression`)
+pub fn resolve_captures(function: &mut FunctionDeclaration) -> Result<(), String> {
+    let mut parameters = HashMap::new();
+    for parameter in &function.parameters {
+        // un parametre ne peut pas etre declare `mut` : on ne le refuse pas
+        parameters.insert(parameter.name.clone(), true);
+    }
+    let mut walker = CaptureWalker {
+        scopes: vec![parameters],
+        closures: Vec::new(),
+    };
+    walker.block(&mut function.body)
+}
+
+struct ClosureState {
+    // indice de la portee des parametres de la fermeture dans `scopes`
+    depth: usize,
+    is_move: bool,
+    captures: Vec<Capture>,
+}
+
+struct CaptureWalker {
+    // variables visibles, de la plus externe a la plus interne, et si elles sont `mut`
+    scopes: Vec<HashMap<String, bool>>,
+    // fermetures en cours d'analyse, de la plus externe a la plus interne
+    closures: Vec<ClosureState>,
+}
+
+impl CaptureWalker {
+    fn declare(&mut self, name: &str, mutable: bool) {
+        self.scopes.last_mut().unwrap().insert(name.to_string(), mutable);
+    }
+
+    fn declare_pattern(&mut self, pattern: &Pattern) {
+        match pattern {
+            Pattern::Identifier(name) => self.declare(name, false),
+            Pattern::MutableIdentifier(name) => self.declare(name, true),
+            Pattern::Binding(name, pattern) => {
+                self.declare(name, false);
+                self.declare_pattern(pattern);
+            }
+            // une alternative peut lier ses noms par un autre sous-motif : `(x, _) | (_, x)`
+            Pattern::Or(alternatives) => {
+                for alternative in alternatives {
+                    self.declare_pattern(alternative);
+                }
+            }
+            Pattern::Struct(pattern) => {
+                for field in &pattern.fields {
+                    self.declare_pattern(&field.pattern);
+                }
+            }
+            Pattern::Tuple(patterns)
+            | Pattern::Array(patterns)
+            | Pattern::TupleRest(patterns)
+            | Pattern::Constructor(_, patterns) => {
+                for pattern in patterns {
+                    self.declare_pattern(pattern);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Utilisation de `name` : chaque fermeture ouverte apres sa declaration le capture
+    fn use_variable(&mut self, name: &str, mode: CaptureMode) -> Result<(), String> {
+        let Some(level) = self.scopes.iter().rposition(|scope| scope.contains_key(name)) else {
+            return Ok(());
+        };
+        let mutable = self.scopes[level][name];
+        let mut captured = false;
+        for closure in self.closures.iter_mut().filter(|closure| closure.depth > level) {
+            captured = true;
+            let mode = if closure.is_move { CaptureMode::Move } else { mode };
+            match closure.captures.iter_mut().find(|capture| capture.name == name) {
+                Some(capture) => capture.mode = capture.mode.max(mode),
+                None => closure.captures.push(Capture { name: name.to_string(), mode }),
+            }
+        }
+        if captured && mode == CaptureMode::MutableReference && !mutable {
+            return Err(format!("Cannot assign to captured variable '{}': it is not declared 'mut'", name));
+        }
+        Ok(())
+    }
+
+    fn scoped<T>(&mut self, visit: impl FnOnce(&mut Self) -> Result<T, String>) -> Result<T, String> {
+        self.scopes.push(HashMap::new());
+        let result = visit(self);
+        self.scopes.pop();
+        result
+    }
+
+    fn block(&mut self, block: &mut [ASTNode]) -> Result<(), String> {
+        block.iter_mut().try_for_each(|node| self.node(node))
+    }
+
+    fn scoped_block(&mut self, block: &mut [ASTNode]) -> Result<(), String> {
+        self.scoped(|walker| walker.block(block))
+    }
+
+    fn node(&mut self, node: &mut ASTNode) -> Result<(), String> {
+        match node {
+            ASTNode::Expression(expression) => self.expression(expression),
+            ASTNode::Statement(statement) => self.statement(statement),
+            ASTNode::Declaration(declaration) => self.declaration(declaration),
+            _ => Ok(()),
+        }
+    }
+
+    fn declaration(&mut self, declaration: &mut Declaration) -> Result<(), String> {
+        match declaration {
+            Declaration::Variable(variable) => {
+                if let Some(value) = &mut variable.value {
+                    self.expression(value)?;
+                }
+                let mutable = variable.mutability == Mutability::Mutable;
+                self.declare(&variable.name, mutable);
+                Ok(())
+            }
+            Declaration::Destructuring(destructuring) => {
+                self.expression(&mut destructuring.value)?;
+                self.declare_pattern(&destructuring.pattern);
+                Ok(())
+            }
+            // une fonction imbriquee ne capture rien : analyse independante
+            Declaration::Function(function) => resolve_captures(function),
+            _ => Ok(()),
+        }
+    }
+
+    fn statement(&mut self, statement: &mut Statement) -> Result<(), String> {
+        match statement {
+            Statement::Expression(expression) => self.expression(expression),
+            Statement::DeclarationStatement(declaration) => self.declaration(declaration),
+            Statement::Assignment(target, value) => {
+                self.expression(value)?;
+                self.assignment_target(target)
+            }
+            Statement::ReturnStatement(return_statement) => match &mut return_statement.value {
+                Some(value) => self.expression(value),
+                None => Ok(()),
+            },
+            Statement::YieldStatement(yield_statement) => match &mut yield_statement.value {
+                Some(value) => self.expression(value),
+                None => Ok(()),
+            },
+            Statement::RaiseStatement(raise) => {
+                for expression in [&mut raise.exception, &mut raise.cause].into_iter().flatten() {
+                    self.expression(expression)?;
+                }
+                Ok(())
+            }
+            Statement::IfStatement(if_statement) => {
+                self.expression(&mut if_statement.condition)?;
+                self.scoped_block(&mut if_statement.then_block)?;
+                for elif in &mut if_statement.elif_block {
+                    self.expression(&mut elif.condition)?;
+                    self.scoped_block(&mut elif.block)?;
+                }
+                match &mut if_statement.else_block {
+                    Some(else_block) => self.scoped_block(else_block),
+                    None => Ok(()),
+                }
+            }
+            Statement::WhileStatement(while_statement) => {
+                self.expression(&mut while_statement.condition)?;
+                self.scoped_block(&mut while_statement.body)
+            }
+            Statement::ForStatement(for_statement) => {
+                self.expression(&mut for_statement.iterable)?;
+                self.scoped(|walker| {
+                    walker.declare(&for_statement.iterator, false);
+                    walker.block(&mut for_statement.body)
+                })
+            }
+            Statement::LoopStatement(loop_statement) => self.scoped_block(&mut loop_statement.body),
+            Statement::WithStatement(with_statement) => {
+                for item in &mut with_statement.items {
+                    self.expression(&mut item.context)?;
+                }
+                self.scoped(|walker| {
+                    for item in &with_statement.items {
+                        if let Some(alias) = &item.alias {
+                            walker.declare(alias, false);
+                        }
+                    }
+                    walker.block(&mut with_statement.body)
+                })
+            }
+            Statement::TryStatement(try_statement) => {
+                self.scoped_block(&mut try_statement.body)?;
+                for handler in &mut try_statement.handlers {
+                    self.scoped(|walker| {
+                        if let Some(name) = &handler.name {
+                            walker.declare(name, false);
+                        }
+                        walker.block(&mut handler.body)
+                    })?;
+                }
+                match &mut try_statement.finally_body {
+                    Some(finally_body) => self.scoped_block(finally_body),
+                    None => Ok(()),
+                }
+            }
+            Statement::MatchStatement(match_statement) => {
+                self.expression(&mut match_statement.expression)?;
+                for arm in &mut match_statement.arms {
+                    self.scoped(|walker| {
+                        walker.declare_pattern(&arm.pattern);
+                        if let Some(guard) = &mut arm.guard {
+                            walker.expression(guard)?;
+                        }
+                        walker.block(&mut arm.body)
+                    })?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Cible d'une affectation simple : un nom encore inconnu y est declare (`count = 0`
+    /// en mode indentation), un nom connu est modifie
+    fn assignment_target(&mut self, target: &mut Expression) -> Result<(), String> {
+        match target {
+            Expression::Identifier(name) if !self.scopes.iter().any(|scope| scope.contains_key(name.as_str())) => {
+                self.declare(name, true);
+                Ok(())
+            }
+            Expression::Tuple(tuple) => tuple.elements.iter_mut().try_for_each(|element| self.assignment_target(element)),
+            other => self.target(other),
+        }
+    }
+
+    /// Cible d'une affectation : la variable a sa racine est modifiee
+    fn target(&mut self, target: &mut Expression) -> Result<(), String> {
+        match target {
+            Expression::Identifier(name) => self.use_variable(name, CaptureMode::MutableReference),
+            Expression::MemberAccess(access) => self.target(&mut access.object),
+            Expression::TupleAccess(access) => self.target(&mut access.tuple),
+            Expression::IndexAccess(access) => {
+                self.target(&mut access.array)?;
+                self.expression(&mut access.index)
+            }
+            Expression::DictAccess(access) => {
+                self.target(&mut access.dict)?;
+                self.expression(&mut access.key)
+            }
+            Expression::CollectionAccess(
+                CollectionAccess::Array(receiver, key)
+                | CollectionAccess::Dict(receiver, key)
+                | CollectionAccess::Unknown(receiver, key),
+            ) => {
+                self.target(receiver)?;
+                self.expression(key)
+            }
+            Expression::Tuple(tuple) => tuple.elements.iter_mut().try_for_each(|element| self.target(element)),
+            other => self.expression(other),
+        }
+    }
+
+    fn closure(&mut self, lambda: &mut LambdaExpression) -> Result<(), String> {
+        self.closures.push(ClosureState {
+            depth: self.scopes.len(),
+            is_move: lambda.is_move,
+            captures: Vec::new(),
+        });
+        let result = self.scoped(|walker| {
+            for parameter in &lambda.parameters {
+                walker.declare(&parameter.name, false);
+            }
+            walker.block(&mut lambda.body)
+        });
+        let state = self.closures.pop().unwrap();
+        lambda.captures = state.captures;
+        result
+    }
+
+    fn expressions(&mut self, expressions: &mut [Expression]) -> Result<(), String> {
+        expressions.iter_mut().try_for_each(|expression| self.expression(expression))
+    }
+
+    fn expression(&mut self, expression: &mut Expression) -> Result<(), String> {
+        match expression {
+            Expression::Identifier(name) => self.use_variable(name, CaptureMode::Reference),
+            Expression::LambdaExpression(lambda) => self.closure(lambda),
+            Expression::Assignment(assignment) => {
+                self.expression(&mut assignment.value)?;
+                self.assignment_target(&mut assignment.target)
+            }
+            Expression::CompoundAssignment(assignment) => {
+                self.target(&mut assignment.target)?;
+                self.expression(&mut assignment.value)
+            }
+            Expression::DestructuringAssignment(assignment) => {
+                self.expression(&mut assignment.value)?;
+                assignment.targets.iter_mut().try_for_each(|target| self.assignment_target(target))
+            }
+            Expression::NamedExpression(named) => {
+                self.expression(&mut named.value)?;
+                self.declare(&named.name, true);
+                Ok(())
+            }
+            Expression::LetCondition(condition) => {
+                self.expression(&mut condition.value)?;
+                self.declare_pattern(&condition.pattern);
+                Ok(())
+            }
+            Expression::Borrow(borrow) => match borrow.borrowed_type {
+                BorrowType::Mutable => self.target(&mut borrow.borrowed_value),
+                BorrowType::Immutable => self.expression(&mut borrow.borrowed_value),
+            },
+            Expression::BinaryOperation(operation) => {
+                self.expression(&mut operation.left)?;
+                self.expression(&mut operation.right)
+            }
+            Expression::ChainedComparison(chain) => self.expressions(&mut chain.operands),
+            Expression::UnaryOperation(operation) => self.expression(&mut operation.operand),
+            Expression::FunctionCall(call) => {
+                self.expression(&mut call.name)?;
+                self.expressions(&mut call.arguments)?;
+                call.keyword_arguments.iter_mut().try_for_each(|keyword| self.expression(&mut keyword.value))
+            }
+            Expression::MethodCall(call) => {
+                self.expression(&mut call.object)?;
+                self.expressions(&mut call.arguments)?;
+                call.keyword_arguments.iter_mut().try_for_each(|keyword| self.expression(&mut keyword.value))
+            }
+            Expression::MemberAccess(access) => self.expression(&mut access.object),
+            Expression::TupleAccess(access) => self.expression(&mut access.tuple),
+            Expression::Tuple(tuple) => self.expressions(&mut tuple.elements),
+            Expression::Array(array) => self.expressions(&mut array.elements),
+            Expression::ArrayRepeat(repeat) => {
+                self.expression(&mut repeat.value)?;
+                self.expression(&mut repeat.size)
+            }
+            Expression::StructLiteral(literal) => {
+                for field in &mut literal.fields {
+                    self.expression(&mut field.value)?;
+                }
+                match &mut literal.base {
+                    Some(base) => self.expression(base),
+                    None => Ok(()),
+                }
+            }
+            Expression::DictLiteral(dict) => {
+                for entry in &mut dict.entries {
+                    self.expression(&mut entry.key)?;
+                    self.expression(&mut entry.value)?;
+                }
+                Ok(())
+            }
+            Expression::IndexAccess(access) => {
+                self.expression(&mut access.array)?;
+                self.expression(&mut access.index)
+            }
+            Expression::DictAccess(access) => {
+                self.expression(&mut access.dict)?;
+                self.expression(&mut access.key)
+            }
+            Expression::CollectionAccess(
+                CollectionAccess::Array(receiver, key)
+                | CollectionAccess::Dict(receiver, key)
+                | CollectionAccess::Unknown(receiver, key),
+            ) => {
+                self.expression(receiver)?;
+                self.expression(key)
+            }
+            Expression::ArraySlice(slice) => {
+                self.expression(&mut slice.array)?;
+                for bound in [&mut slice.start, &mut slice.end, &mut slice.step].into_iter().flatten() {
+                    self.expression(bound)?;
+                }
+                Ok(())
+            }
+            Expression::RangeExpression(range) => {
+                for bound in [&mut range.left, &mut range.right].into_iter().flatten() {
+                    self.expression(bound)?;
+                }
+                Ok(())
+            }
+            Expression::Conditional(conditional) => {
+                self.expression(&mut conditional.condition)?;
+                self.expression(&mut conditional.then_block)?;
+                self.expression(&mut conditional.else_block)
+            }
+            Expression::Block(block) => self.scoped_block(&mut block.statements),
+            Expression::TypeCast(cast) => self.expression(&mut cast.expression),
+            Expression::Try(try_expression) => self.expression(&mut try_expression.expression),
+            Expression::Await(await_expression) => self.expression(&mut await_expression.expression),
+            Expression::ListComprehension(comprehension) => self.scoped(|walker| {
+                for iterator in &mut comprehension.iterators {
+                    walker.expression(&mut iterator.iterator)?;
+                    walker.declare_pattern(&iterator.pattern);
+                }
+                walker.expressions(&mut comprehension.conditions)?;
+                walker.expression(&mut comprehension.elements)
+            }),
+            Expression::Statement(statement) => self.statement(statement),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! Detection des fonctions generatrices.
+//!
+//! Une fonction dont le corps contient `yield` (ou `yield from`) est un generateur :
+//! son appel n'execute pas le corps mais renvoie un `Iterator<T>`, `T` etant le type
+//! des valeurs produites. Les `yield` des fonctions imbriquees ne comptent pas.
+
+use crate::parser::ast::{ASTNode, Statement, YieldStatement};
+
+/// Instructions `yield` d'un corps de fonction, blocs imbriques compris
+pub fn collect_yields(body: &[ASTNode]) -> Vec<&YieldStatement> {
+    let mut yields = Vec::new();
+    for node in body {
+        collect_node(node, &mut yields);
+    }
+    yields
+}
+
+pub fn is_generator(body: &[ASTNode]) -> bool {
+    !collect_yields(body).is_empty()
+}
+
+fn collect_node<'a>(node: &'a ASTNode, yields: &mut Vec<&'a YieldStatement>) {
+    let ASTNode::Statement(statement) = node else { return };
+    let mut visit = |block: &'a [ASTNode]| {
+        for node in block {
+            collect_node(node, yields);
+        }
+    };
+
+    match statement {
+        Statement::YieldStatement(yield_statement) => yields.push(yield_statement),
+        Statement::IfStatement(if_statement) => {
+            visit(&if_statement.then_block);
+            for elif in &if_statement.elif_block {
+                visit(&elif.block);
+            }
+            if let Some(else_block) = &if_statement.else_block {
+                visit(else_block);
+            }
+        }
+        Statement::WhileStatement(while_statement) => visit(&while_statement.body),
+        Statement::ForStatement(for_statement) => visit(&for_statement.body),
+        Statement::LoopStatement(loop_statement) => visit(&loop_statement.body),
+        Statement::WithStatement(with_statement) => visit(&with_statement.body),
+        Statement::TryStatement(try_statement) => {
+            visit(&try_statement.body);
+            for handler in &try_statement.handlers {
+                visit(&handler.body);
+            }
+            if let Some(finally_body) = &try_statement.finally_body {
+                visit(finally_body);
+            }
+        }
+        Statement::MatchStatement(match_statement) => {
+            for arm in &match_statement.arms {
+                visit(&arm.body);
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,101 @@
+//! Indexation `x[cle]` selon le type de `x`.
+//!
+//! Le parseur ne peut pas distinguer `d[k]` sur un dictionnaire de `a[i]` sur un tableau :
+//! il produit `CollectionAccess::Unknown`, que l'analyse semantique remplace une fois le
+//! type du receveur connu :
+//! - tableaux (`Array<T>`, `List<T>`, `Vec<T>`, `[T]`) et chaines : `IndexAccess`, cle `int`
+//! - dictionnaires (`Dict<K, V>`, `HashMap<K, V>`) : `DictAccess`, cle de type `K`
+//! - types utilisateur : appel de leur methode `index` (trait `Index`)
+//!
+//! Une tranche (`a[1:5:2]`, `a[..3]`) a le type de son receveur : tableau ou chaine,
+//! avec des bornes et un pas entiers.
+
+use crate::parser::ast::{DictAccess, Expression, IndexAccess, MethodCall, Type};
+
+/// Sorte d'indexation retenue pour un receveur
+#[derive(Debug, Clone, PartialEq)]
+pub enum IndexKind {
+    /// indexation par position, donne le type d'element
+    Sequence(Type),
+    /// indexation par cle, donne le type de valeur
+    Mapping(Type),
+    /// type utilisateur, resolu par sa methode `index`
+    Custom,
+    /// receveur de type encore inconnu
+    Unresolved,
+}
+
+/// Verifie `receiver[key]` et choisit la sorte d'indexation
+pub fn index_kind(receiver: &Type, key: &Type) -> Result<IndexKind, String> {
+    let (kind, expected_key) = match receiver {
+        Type::Infer => return Ok(IndexKind::Unresolved),
+        Type::Reference(inner) | Type::ReferenceMutable(inner) => return index_kind(inner, key),
+        Type::Named(_) | Type::Custom(_) => return Ok(IndexKind::Custom),
+        Type::Array(element) => (IndexKind::Sequence((**element).clone()), Type::Int),
+        Type::String => (IndexKind::Sequence(Type::Char), Type::Int),
+        Type::Generic(generic) => {
+            let parameter = |index: usize| generic.type_parameters.get(index).cloned().unwrap_or(Type::Infer);
+            match generic.base.as_str() {
+                "Array" | "List" | "Vec" => (IndexKind::Sequence(parameter(0)), Type::Int),
+                "Dict" | "HashMap" => (IndexKind::Mapping(parameter(1)), parameter(0)),
+                _ => return Ok(IndexKind::Custom),
+            }
+        }
+        other => return Err(format!("Type {:?} cannot be indexed", other)),
+    };
+
+    if *key != Type::Infer && expected_key != Type::Infer && *key != expected_key {
+        return Err(match kind {
+            IndexKind::Mapping(_) => format!("Dict key must be {:?}, found {:?}", expected_key, key),
+            _ => format!("Index of {:?} must be {:?}, found {:?}", receiver, expected_key, key),
+        });
+    }
+    Ok(kind)
+}
+
+/// Type de `receiver[key]`
+pub fn index_type(receiver: &Type, key: &Type) -> Result<Type, String> {
+    match index_kind(receiver, key)? {
+        IndexKind::Sequence(element) => Ok(element),
+        IndexKind::Mapping(value) => Ok(value),
+        IndexKind::Custom | IndexKind::Unresolved => Ok(Type::Infer),
+    }
+}
+
+/// Noeud qui remplace `receiver[key]` ; None tant que le type du receveur est inconnu
+pub fn rewrite_index(kind: &IndexKind, receiver: Expression, key: Expression) -> Option<Expression> {
+    match kind {
+        IndexKind::Sequence(_) => Some(Expression::IndexAccess(IndexAccess {
+            array: Box::new(receiver),
+            index: Box::new(key),
+        })),
+        IndexKind::Mapping(_) => Some(Expression::DictAccess(DictAccess {
+            dict: Box::new(receiver),
+            key: Box::new(key),
+        })),
+        IndexKind::Custom => Some(Expression::MethodCall(MethodCall {
+            object: Box::new(receiver),
+            method: "index".to_string(),
+            arguments: vec![key],
+            keyword_arguments: Vec::new(),
+        })),
+        IndexKind::Unresolved => None,
+    }
+}
+
+/// Type de la tranche `receiver[start:end:step]` ; chaque borne absente vaut None
+pub fn slice_type(receiver: &Type, bounds: [Option<&Type>; 3]) -> Result<Type, String> {
+    for (name, bound) in ["start", "end", "step"].into_iter().zip(bounds) {
+        match bound {
+            Some(Type::Int | Type::Infer) | None => {}
+            Some(other) => return Err(format!("Slice {} must be Int, found {:?}", name, other)),
+        }
+    }
+
+    match receiver {
+        Type::Reference(inner) | Type::ReferenceMutable(inner) => slice_type(inner, bounds),
+        Type::Array(_) | Type::String | Type::Infer | Type::Named(_) | Type::Custom(_) => Ok(receiver.clone()),
+        Type::Generic(generic) if matches!(generic.base.as_str(), "Array" | "List" | "Vec") => Ok(receiver.clone()),
+        other => Err(format!("Type {:?} cannot be sliced", other)),
+    }
+}
@@ -3,3 +3,10 @@ mod semantic_error;
 mod ast_semantic;
 mod type_checker;
 mod borrow_check;
+pub mod arguments;
+pub mod builtins;
+pub mod cast;
+pub mod closures;
+pub mod generators;
+pub mod indexing;
+pub mod visibility;
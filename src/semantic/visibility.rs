@@ -0,0 +1,541 @@
+//! Verification de la visibilite entre modules.
+//!
+//! Comme en Rust, un element prive (fonction, constante, champ de structure,
+//! attribut ou methode de classe, sous-module) n'est accessible que depuis le module
+//! qui le declare et ses sous-modules. Un element `pub` est accessible partout.
+//!
+//! Les modules sont designes par leur chemin (`["main", "geometrie", "interne"]`) ;
+//! les modules en ligne (`mod nom { ... }`) prolongent le chemin du module englobant.
+
+use std::collections::HashMap;
+
+use crate::parser::ast::{ASTNode, Declaration, Expression, Parameter, Pattern, Statement, Type, Visibility};
+use crate::parser::inference::pattern_names;
+
+#[derive(Debug, Clone)]
+enum ItemKind {
+    Module(HashMap<String, Item>),
+    /// structure ou classe : visibilite et type de chaque champ, attribut ou methode
+    Type(HashMap<String, (Visibility, Option<Type>)>),
+    Value,
+}
+
+#[derive(Debug, Clone)]
+struct Item {
+    name: String,
+    visibility: Visibility,
+    /// chemin du module qui declare l'element
+    module: Vec<String>,
+    kind: ItemKind,
+}
+
+/// Verificateur de visibilite pour un module et les elements qu'il importe
+pub struct VisibilityChecker {
+    path: Vec<String>,
+    imports: HashMap<String, Item>,
+}
+
+impl VisibilityChecker {
+    /// `module` est le chemin pointe du module verifie (`a.b`)
+    pub fn new(module: &str) -> Self {
+        VisibilityChecker {
+            path: module.split('.').map(str::to_string).collect(),
+            imports: HashMap::new(),
+        }
+    }
+
+    /// Rend `declaration`, declaree dans le module `module`, visible sous le nom `local`
+    pub fn with_import(mut self, local: &str, module: &str, declaration: &Declaration) -> Self {
+        let module: Vec<String> = module.split('.').map(str::to_string).collect();
+        if let Some(mut item) = item_of(declaration, &module) {
+            item.name = local.to_string();
+            self.imports.insert(local.to_string(), item);
+        }
+        self
+    }
+
+    /// Rend le module `module` (de contenu `body`) visible sous le nom `local`
+    pub fn with_module(mut self, local: &str, module: &str, body: &[ASTNode]) -> Self {
+        let path: Vec<String> = module.split('.').map(str::to_string).collect();
+        let items = collect_items(body, &path);
+        let parent = path[..path.len() - 1].to_vec();
+        self.imports.insert(local.to_string(), Item {
+            name: local.to_string(),
+            visibility: Visibility::Public,
+            module: parent,
+            kind: ItemKind::Module(items),
+        });
+        self
+    }
+
+    pub fn check(&self, body: &[ASTNode]) -> Result<(), String> {
+        let mut root = collect_items(body, &self.path);
+        for (name, item) in &self.imports {
+            root.entry(name.clone()).or_insert_with(|| item.clone());
+        }
+
+        let mut walker = Walker {
+            root: &root,
+            base: self.path.len(),
+            path: self.path.clone(),
+            locals: vec![HashMap::new()],
+        };
+        walker.check_body(body)
+    }
+}
+
+/// Verifie un programme d'un seul fichier, considere comme le module `main`
+pub fn check_visibility(body: &[ASTNode]) -> Result<(), String> {
+    VisibilityChecker::new("main").check(body)
+}
+
+fn declaration_of(node: &ASTNode) -> Option<&Declaration> {
+    match node {
+        ASTNode::Declaration(declaration) => Some(declaration),
+        ASTNode::Statement(Statement::DeclarationStatement(declaration)) => Some(declaration),
+        _ => None,
+    }
+}
+
+fn collect_items(body: &[ASTNode], module: &[String]) -> HashMap<String, Item> {
+    body.iter()
+        .filter_map(declaration_of)
+        .filter_map(|declaration| item_of(declaration, module))
+        .map(|item| (item.name.clone(), item))
+        .collect()
+}
+
+fn item_of(declaration: &Declaration, module: &[String]) -> Option<Item> {
+    let (name, visibility, kind) = match declaration {
+        Declaration::Function(function) => (&function.name, function.visibility.clone(), ItemKind::Value),
+        Declaration::Constante(constant) => (&constant.name, constant.visibility.clone(), ItemKind::Value),
+        Declaration::Enum(enumeration) => (&enumeration.name, enumeration.visibility.clone(), ItemKind::Value),
+        Declaration::Trait(trait_declaration) => (&trait_declaration.name, trait_declaration.visibility.clone(), ItemKind::Value),
+        Declaration::Structure(structure) => {
+            let fields = structure
+                .fields
+                .iter()
+                .map(|field| (field.name.clone(), (field.visibility.clone(), Some(field.field_type.clone()))))
+                .collect();
+            (&structure.name, structure.visibility.clone(), ItemKind::Type(fields))
+        }
+        Declaration::Class(class) => {
+            let mut members: HashMap<String, (Visibility, Option<Type>)> = class
+                .attributes
+                .iter()
+                .map(|attribute| (attribute.name.clone(), (attribute.visibility.clone(), Some(attribute.attr_type.clone()))))
+                .collect();
+            for method in &class.methods {
+                members.insert(method.name.clone(), (method.visibility.clone(), None));
+            }
+            (&class.name, class.visibility.clone(), ItemKind::Type(members))
+        }
+        Declaration::Module(inner) => {
+            let mut path = module.to_vec();
+            path.push(inner.name.clone());
+            (&inner.name, inner.visibility.clone(), ItemKind::Module(collect_items(&inner.body, &path)))
+        }
+        _ => return None,
+    };
+    Some(Item {
+        name: name.clone(),
+        visibility,
+        module: module.to_vec(),
+        kind,
+    })
+}
+
+/// Ce que designe une expression, quand on le sait
+#[derive(Clone, Copy)]
+enum Target<'a> {
+    Item(&'a Item),
+    /// valeur dont le type est une structure ou une classe connue
+    Instance(&'a Item),
+}
+
+struct Walker<'a> {
+    root: &'a HashMap<String, Item>,
+    // longueur du chemin du fichier verifie, dont `root` contient les elements
+    base: usize,
+    // module courant, modules en ligne compris
+    path: Vec<String>,
+    // variables locales et type connu de chacune (None si inconnu)
+    locals: Vec<HashMap<String, Option<&'a Item>>>,
+}
+
+impl<'a> Walker<'a> {
+    /// Elements du module `path`, ou None si ce n'est pas un module connu
+    fn module_items(&self, path: &[String]) -> Option<&'a HashMap<String, Item>> {
+        let mut items = self.root;
+        for segment in path.iter().skip(self.base) {
+            match &items.get(segment)?.kind {
+                ItemKind::Module(children) => items = children,
+                _ => return None,
+            }
+        }
+        Some(items)
+    }
+
+    /// Nom d'element vu depuis le module courant : module courant puis modules englobants
+    fn lookup(&self, name: &str) -> Option<&'a Item> {
+        for end in (self.base..=self.path.len()).rev() {
+            if let Some(item) = self.module_items(&self.path[..end]).and_then(|items| items.get(name)) {
+                return Some(item);
+            }
+        }
+        None
+    }
+
+    fn lookup_type(&self, ty: &Type) -> Option<&'a Item> {
+        let Type::Named(name) = ty else { return None };
+        self.lookup(name).filter(|item| matches!(item.kind, ItemKind::Type(_)))
+    }
+
+    /// Un element prive n'est accessible que depuis son module et ses sous-modules
+    fn accessible(&self, visibility: &Visibility, module: &[String]) -> bool {
+        *visibility == Visibility::Public || self.path.starts_with(module)
+    }
+
+    fn declare_local(&mut self, name: &str, ty: Option<&'a Item>) {
+        self.locals.last_mut().unwrap().insert(name.to_string(), ty);
+    }
+
+    fn local(&self, name: &str) -> Option<Option<&'a Item>> {
+        self.locals.iter().rev().find_map(|scope| scope.get(name).copied())
+    }
+
+    fn check_body(&mut self, body: &'a [ASTNode]) -> Result<(), String> {
+        for node in body {
+            self.check_node(node)?;
+        }
+        Ok(())
+    }
+
+    fn check_scoped(&mut self, body: &'a [ASTNode], bindings: Vec<(String, Option<&'a Item>)>) -> Result<(), String> {
+        self.locals.push(bindings.into_iter().collect());
+        let result = self.check_body(body);
+        self.locals.pop();
+        result
+    }
+
+    fn check_node(&mut self, node: &'a ASTNode) -> Result<(), String> {
+        match node {
+            ASTNode::Declaration(declaration) => self.check_declaration(declaration),
+            ASTNode::Statement(statement) => self.check_statement(statement),
+            ASTNode::Expression(expression) => self.check_expression(expression).map(|_| ()),
+            ASTNode::Program(body) => self.check_body(body),
+            _ => Ok(()),
+        }
+    }
+
+    /// Motif d'un `match` ou d'un `if let` : renvoie les noms qu'il lie
+    fn check_pattern(&self, pattern: &Pattern) -> Result<Vec<(String, Option<&'a Item>)>, String> {
+        self.check_pattern_fields(pattern)?;
+        let mut names = Vec::new();
+        pattern_names(pattern, &mut names);
+        Ok(names.into_iter().map(|name| (name, None)).collect())
+    }
+
+    /// Nommer un champ dans un motif de structure revient a y acceder
+    fn check_pattern_fields(&self, pattern: &Pattern) -> Result<(), String> {
+        match pattern {
+            Pattern::Struct(structure) => {
+                let owner = self.lookup(&structure.name).map(Target::Instance);
+                for field in &structure.fields {
+                    self.check_member(owner, &field.name)?;
+                    self.check_pattern_fields(&field.pattern)?;
+                }
+                Ok(())
+            }
+            Pattern::Tuple(patterns)
+            | Pattern::Array(patterns)
+            | Pattern::TupleRest(patterns)
+            | Pattern::Or(patterns)
+            | Pattern::Constructor(_, patterns) => patterns.iter().try_for_each(|pattern| self.check_pattern_fields(pattern)),
+            Pattern::Binding(_, pattern) => self.check_pattern_fields(pattern),
+            _ => Ok(()),
+        }
+    }
+
+    fn parameters(&self, parameters: &'a [Parameter]) -> Vec<(String, Option<&'a Item>)> {
+        parameters
+            .iter()
+            .map(|parameter| (parameter.name.clone(), self.lookup_type(&parameter.parameter_type)))
+            .collect()
+    }
+
+    fn check_declaration(&mut self, declaration: &'a Declaration) -> Result<(), String> {
+        match declaration {
+            Declaration::Variable(variable) => {
+                let value_type = match &variable.value {
+                    Some(value) => self.check_expression(value)?,
+                    None => None,
+                };
+                let declared = variable.variable_type.as_ref().and_then(|ty| self.lookup_type(ty));
+                let ty = declared.or(match value_type {
+                    Some(Target::Instance(item)) => Some(item),
+                    _ => None,
+                });
+                self.declare_local(&variable.name, ty);
+                Ok(())
+            }
+            Declaration::Destructuring(destructuring) => {
+                self.check_expression(&destructuring.value)?;
+                let mut names = Vec::new();
+                pattern_names(&destructuring.pattern, &mut names);
+                for name in names {
+                    self.declare_local(&name, None);
+                }
+                Ok(())
+            }
+            Declaration::Constante(constant) => self.check_expression(&constant.value).map(|_| ()),
+            Declaration::Function(function) => {
+                let bindings = self.parameters(&function.parameters);
+                self.check_scoped(&function.body, bindings)
+            }
+            Declaration::Class(class) => {
+                let this = self.lookup(&class.name);
+                if let Some(constructor) = &class.constructor {
+                    let mut bindings = self.parameters(&constructor.parameters);
+                    bindings.push(("self".to_string(), this));
+                    self.check_scoped(&constructor.body, bindings)?;
+                }
+                for method in &class.methods {
+                    let mut bindings = self.parameters(&method.parameters);
+                    bindings.push(("self".to_string(), this));
+                    self.check_scoped(&method.body, bindings)?;
+                }
+                Ok(())
+            }
+            Declaration::Impl(implementation) => {
+                let this = self.lookup_type(&implementation.target_type);
+                for method in &implementation.methods {
+                    let mut bindings = self.parameters(&method.parameters);
+                    bindings.push(("self".to_string(), this));
+                    self.check_scoped(&method.body, bindings)?;
+                }
+                Ok(())
+            }
+            Declaration::Module(module) => {
+                self.path.push(module.name.clone());
+                let locals = std::mem::replace(&mut self.locals, vec![HashMap::new()]);
+                let result = self.check_body(&module.body);
+                self.locals = locals;
+                self.path.pop();
+                result
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn check_statement(&mut self, statement: &'a Statement) -> Result<(), String> {
+        match statement {
+            Statement::DeclarationStatement(declaration) => self.check_declaration(declaration),
+            Statement::Expression(expression) => self.check_expression(expression).map(|_| ()),
+            Statement::Assignment(target, value) => {
+                self.check_expression(target)?;
+                self.check_expression(value).map(|_| ())
+            }
+            Statement::ReturnStatement(return_statement) => match &return_statement.value {
+                Some(value) => self.check_expression(value).map(|_| ()),
+                None => Ok(()),
+            },
+            Statement::YieldStatement(yield_statement) => match &yield_statement.value {
+                Some(value) => self.check_expression(value).map(|_| ()),
+                None => Ok(()),
+            },
+            Statement::IfStatement(if_statement) => {
+                self.check_expression(&if_statement.condition)?;
+                self.check_scoped(&if_statement.then_block, Vec::new())?;
+                for elif in &if_statement.elif_block {
+                    self.check_expression(&elif.condition)?;
+                    self.check_scoped(&elif.block, Vec::new())?;
+                }
+                match &if_statement.else_block {
+                    Some(else_block) => self.check_scoped(else_block, Vec::new()),
+                    None => Ok(()),
+                }
+            }
+            Statement::WhileStatement(while_statement) => {
+                self.check_expression(&while_statement.condition)?;
+                self.check_scoped(&while_statement.body, Vec::new())
+            }
+            Statement::ForStatement(for_statement) => {
+                self.check_expression(&for_statement.iterable)?;
+                self.check_scoped(&for_statement.body, vec![(for_statement.iterator.clone(), None)])
+            }
+            Statement::LoopStatement(loop_statement) => self.check_scoped(&loop_statement.body, Vec::new()),
+            Statement::WithStatement(with_statement) => {
+                let mut bindings = Vec::new();
+                for item in &with_statement.items {
+                    self.check_expression(&item.context)?;
+                    if let Some(alias) = &item.alias {
+                        bindings.push((alias.clone(), None));
+                    }
+                }
+                self.check_scoped(&with_statement.body, bindings)
+            }
+            Statement::TryStatement(try_statement) => {
+                self.check_scoped(&try_statement.body, Vec::new())?;
+                for handler in &try_statement.handlers {
+                    let bindings = handler.name.iter().map(|name| (name.clone(), None)).collect();
+                    self.check_scoped(&handler.body, bindings)?;
+                }
+                match &try_statement.finally_body {
+                    Some(finally_body) => self.check_scoped(finally_body, Vec::new()),
+                    None => Ok(()),
+                }
+            }
+            Statement::MatchStatement(match_statement) => {
+                self.check_expression(&match_statement.expression)?;
+                for arm in &match_statement.arms {
+                    let bindings = self.check_pattern(&arm.pattern)?;
+                    self.check_scoped(&arm.body, bindings)?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Acces `owner.member` : sous-element d'un module ou membre d'une instance
+    fn check_member(&self, owner: Option<Target<'a>>, member: &str) -> Result<Option<Target<'a>>, String> {
+        match owner {
+            Some(Target::Item(Item { kind: ItemKind::Module(items), .. })) => {
+                let Some(item) = items.get(member) else { return Ok(None) };
+                if !self.accessible(&item.visibility, &item.module) {
+                    return Err(format!(
+                        "'{}' is private to module '{}'",
+                        member,
+                        item.module.join(".")
+                    ));
+                }
+                Ok(Some(Target::Item(item)))
+            }
+            Some(Target::Instance(item)) => {
+                let ItemKind::Type(members) = &item.kind else { return Ok(None) };
+                let Some((visibility, member_type)) = members.get(member) else { return Ok(None) };
+                if !self.accessible(visibility, &item.module) {
+                    return Err(format!(
+                        "Member '{}' of '{}' is private to module '{}'",
+                        member,
+                        item.name,
+                        item.module.join(".")
+                    ));
+                }
+                Ok(member_type.as_ref().and_then(|ty| self.lookup_type(ty)).map(Target::Instance))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn check_expression(&mut self, expression: &'a Expression) -> Result<Option<Target<'a>>, String> {
+        match expression {
+            Expression::Identifier(name) => match self.local(name) {
+                Some(ty) => Ok(ty.map(Target::Instance)),
+                None => Ok(self.lookup(name).map(Target::Item)),
+            },
+            Expression::MemberAccess(access) => {
+                let owner = self.check_expression(&access.object)?;
+                self.check_member(owner, &access.member)
+            }
+            Expression::MethodCall(call) => {
+                let owner = self.check_expression(&call.object)?;
+                let callee = self.check_member(owner, &call.method)?;
+                self.check_expressions(&call.arguments)?;
+                for keyword in &call.keyword_arguments {
+                    self.check_expression(&keyword.value)?;
+                }
+                Ok(constructed(callee))
+            }
+            Expression::FunctionCall(call) => {
+                let callee = self.check_expression(&call.name)?;
+                self.check_expressions(&call.arguments)?;
+                for keyword in &call.keyword_arguments {
+                    self.check_expression(&keyword.value)?;
+                }
+                Ok(constructed(callee))
+            }
+            Expression::BinaryOperation(operation) => {
+                self.check_expression(&operation.left)?;
+                self.check_expression(&operation.right)?;
+                Ok(None)
+            }
+            Expression::ChainedComparison(chain) => self.check_expressions(&chain.operands),
+            Expression::UnaryOperation(operation) => self.check_expression(&operation.operand).map(|_| None),
+            Expression::TypeCast(cast) => self.check_expression(&cast.expression).map(|_| None),
+            Expression::Try(try_expression) => self.check_expression(&try_expression.expression).map(|_| None),
+            Expression::Await(await_expression) => self.check_expression(&await_expression.expression).map(|_| None),
+            Expression::Conditional(conditional) => {
+                self.check_expression(&conditional.condition)?;
+                self.check_expression(&conditional.then_block)?;
+                self.check_expression(&conditional.else_block)?;
+                Ok(None)
+            }
+            Expression::Assignment(assignment) => {
+                self.check_expression(&assignment.target)?;
+                self.check_expression(&assignment.value).map(|_| None)
+            }
+            Expression::CompoundAssignment(assignment) => {
+                self.check_expression(&assignment.target)?;
+                self.check_expression(&assignment.value).map(|_| None)
+            }
+            Expression::NamedExpression(named) => {
+                let value = self.check_expression(&named.value)?;
+                let ty = match value {
+                    Some(Target::Instance(item)) => Some(item),
+                    _ => None,
+                };
+                self.declare_local(&named.name, ty);
+                Ok(value)
+            }
+            Expression::LetCondition(condition) => {
+                self.check_expression(&condition.value)?;
+                for (name, ty) in self.check_pattern(&condition.pattern)? {
+                    self.declare_local(&name, ty);
+                }
+                Ok(None)
+            }
+            Expression::IndexAccess(access) => {
+                self.check_expression(&access.array)?;
+                self.check_expression(&access.index).map(|_| None)
+            }
+            Expression::Array(array) => self.check_expressions(&array.elements),
+            Expression::Tuple(tuple) => self.check_expressions(&tuple.elements),
+            Expression::TupleAccess(access) => self.check_expression(&access.tuple).map(|_| None),
+            Expression::StructLiteral(literal) => {
+                // initialiser un champ revient a y acceder
+                let owner = self.lookup(&literal.name).map(Target::Instance);
+                for field in &literal.fields {
+                    self.check_member(owner, &field.name)?;
+                    self.check_expression(&field.value)?;
+                }
+                if let Some(base) = &literal.base {
+                    self.check_expression(base)?;
+                }
+                Ok(owner)
+            }
+            Expression::Block(block) => self.check_scoped(&block.statements, Vec::new()).map(|_| None),
+            Expression::LambdaExpression(lambda) => {
+                let bindings = self.parameters(&lambda.parameters);
+                self.check_scoped(&lambda.body, bindings).map(|_| None)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn check_expressions(&mut self, expressions: &'a [Expression]) -> Result<Option<Target<'a>>, String> {
+        for expression in expressions {
+            self.check_expression(expression)?;
+        }
+        Ok(None)
+    }
+}
+
+/// L'appel d'une structure ou d'une classe construit une instance
+fn constructed(callee: Option<Target<'_>>) -> Option<Target<'_>> {
+    match callee {
+        Some(Target::Item(item)) if matches!(item.kind, ItemKind::Type(_)) => Some(Target::Instance(item)),
+        _ => None,
+    }
+}
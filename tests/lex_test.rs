@@ -1427,6 +1427,290 @@ def main():
         assert_eq!(lexer.get_token(), Some(TokenType::EOF));
     }
 
+    #[test]
+    fn test_unicode_identifiers() {
+        let mut lexer = Lexer::new("let élève = calculer_durée", SyntaxMode::Braces);
+        assert_eq!(lexer.get_token(), Some(TokenType::KEYWORD(Keywords::LET)));
+        assert_eq!(
+            lexer.get_token(),
+            Some(TokenType::IDENTIFIER {
+                name: "élève".to_string()
+            })
+        );
+        assert_eq!(lexer.get_token(), Some(TokenType::OPERATOR(Operators::EQUAL)));
+        assert_eq!(
+            lexer.get_token(),
+            Some(TokenType::IDENTIFIER {
+                name: "calculer_durée".to_string()
+            })
+        );
+        assert_eq!(lexer.get_token(), Some(TokenType::EOF));
+    }
+
+    #[test]
+    fn test_unicode_identifier_nfc_normalization() {
+        // "e" + accent combinant doit donner le meme identifiant que "é" precompose
+        let mut lexer = Lexer::new("e\u{301}l\u{e8}ve", SyntaxMode::Braces);
+        assert_eq!(
+            lexer.get_token(),
+            Some(TokenType::IDENTIFIER {
+                name: "\u{e9}l\u{e8}ve".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_mixed_script_identifier() {
+        // "pаypal" avec un 'а' cyrillique
+        let mut lexer = Lexer::new("p\u{430}ypal", SyntaxMode::Braces);
+        if let Some(TokenType::ERROR(error)) = lexer.get_token() {
+            assert_eq!(
+                error.error,
+                LexerErrorType::MixedScriptIdentifier("p\u{430}ypal".to_string())
+            );
+        } else {
+            panic!("Expected an ERROR token for mixed-script identifier");
+        }
+    }
+
+    #[test]
+    fn test_confusable_identifier_warning() {
+        // "о" cyrillique seul est confondable avec "o" latin
+        let mut lexer = Lexer::new("o = \u{43e}", SyntaxMode::Braces);
+        let _ = lexer.tokenize();
+        assert_eq!(lexer.warnings().len(), 1);
+        assert_eq!(
+            lexer.warnings()[0].error,
+            LexerErrorType::ConfusableIdentifier("\u{43e}".to_string(), "o".to_string())
+        );
+    }
+
+    #[test]
+    fn test_lexer_as_iterator() {
+        let lexer = Lexer::new("let x = 5", SyntaxMode::Braces);
+        let token_types: Vec<TokenType> = lexer.map(|token| token.token_type).collect();
+        assert_eq!(
+            token_types,
+            vec![
+                TokenType::KEYWORD(Keywords::LET),
+                TokenType::IDENTIFIER {
+                    name: "x".to_string()
+                },
+                TokenType::OPERATOR(Operators::EQUAL),
+                TokenType::INTEGER {
+                    value: BigInt::from(5)
+                },
+                TokenType::EOF,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_with_errors() {
+        let mut lexer = Lexer::new("a $ b", SyntaxMode::Braces);
+        let (tokens, errors) = lexer.tokenize_with_errors();
+
+        assert!(tokens
+            .iter()
+            .all(|token| !matches!(token.token_type, TokenType::ERROR(_))));
+        assert_eq!(tokens.len(), 3); // a, b, EOF
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, LexerErrorType::InvalidToken("$".to_string()));
+    }
+
+    #[test]
+    fn test_implicit_line_joining_in_brackets() {
+        let source = "f(a,\n        b)\nx = [1,\n  2]\n";
+        let lexer = Lexer::new(source, SyntaxMode::Indentation);
+        let token_types: Vec<TokenType> = lexer.map(|token| token.token_type).collect();
+
+        assert!(!token_types
+            .iter()
+            .any(|t| matches!(t, TokenType::INDENT | TokenType::DEDENT)));
+        let newlines = token_types
+            .iter()
+            .filter(|t| matches!(t, TokenType::NEWLINE))
+            .count();
+        assert_eq!(newlines, 2);
+    }
+
+    #[test]
+    fn test_backslash_line_continuation() {
+        let source = "x = 1 + \\\n    2\n";
+        let mut lexer = Lexer::new(source, SyntaxMode::Indentation);
+        assert_eq!(
+            lexer.get_token(),
+            Some(TokenType::IDENTIFIER {
+                name: "x".to_string()
+            })
+        );
+        assert_eq!(lexer.get_token(), Some(TokenType::OPERATOR(Operators::EQUAL)));
+        assert_eq!(
+            lexer.get_token(),
+            Some(TokenType::INTEGER {
+                value: BigInt::from(1)
+            })
+        );
+        assert_eq!(lexer.get_token(), Some(TokenType::OPERATOR(Operators::PLUS)));
+        assert_eq!(
+            lexer.get_token(),
+            Some(TokenType::INTEGER {
+                value: BigInt::from(2)
+            })
+        );
+        assert_eq!(lexer.get_token(), Some(TokenType::NEWLINE));
+        assert_eq!(lexer.get_token(), Some(TokenType::EOF));
+    }
+
+    #[test]
+    fn test_dedent_closes_several_blocks() {
+        let source = "if a:\n    if b:\n        x\ny\n";
+        let lexer = Lexer::new(source, SyntaxMode::Indentation);
+        let layout: Vec<TokenType> = lexer
+            .map(|token| token.token_type)
+            .filter(|t| matches!(t, TokenType::INDENT | TokenType::DEDENT))
+            .collect();
+        assert_eq!(
+            layout,
+            vec![
+                TokenType::INDENT,
+                TokenType::INDENT,
+                TokenType::DEDENT,
+                TokenType::DEDENT
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inconsistent_dedent() {
+        let source = "if a:\n    x\n  y\n";
+        let mut lexer = Lexer::new(source, SyntaxMode::Indentation);
+        let (_, errors) = lexer.tokenize_with_errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].error,
+            LexerErrorType::IndentationError {
+                found: 2,
+                expected: vec![0, 4]
+            }
+        );
+        assert_eq!(errors[0].position.line, 3);
+    }
+
+    #[test]
+    fn test_inconsistent_dedent_keeps_blocks_balanced() {
+        let source = "if a:\n    if b:\n        x\n  y\nif c:\n    z\n";
+        let mut lexer = Lexer::new(source, SyntaxMode::Indentation);
+        let (tokens, errors) = lexer.tokenize_with_errors();
+        assert_eq!(errors.len(), 1);
+        // les deux blocs fermes par la ligne fautive recoivent leur DEDENT
+        let after_error: Vec<TokenType> = tokens
+            .into_iter()
+            .map(|token| token.token_type)
+            .skip_while(|t| *t != TokenType::IDENTIFIER { name: "x".to_string() })
+            .skip(1)
+            .filter(|t| !matches!(t, TokenType::NEWLINE | TokenType::EOF))
+            .collect();
+        assert_eq!(
+            after_error,
+            vec![
+                TokenType::DEDENT,
+                TokenType::DEDENT,
+                TokenType::IDENTIFIER { name: "y".to_string() },
+                TokenType::KEYWORD(Keywords::IF),
+                TokenType::IDENTIFIER { name: "c".to_string() },
+                TokenType::DELIMITER(Delimiters::COLON),
+                TokenType::INDENT,
+                TokenType::IDENTIFIER { name: "z".to_string() },
+                TokenType::DEDENT,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_comment_lines_do_not_affect_indentation() {
+        let source = "def f():\n    if x:\n        y = 1\n  # comment\n    z = 2\n";
+        let mut lexer = Lexer::new(source, SyntaxMode::Indentation);
+        let (tokens, errors) = lexer.tokenize_with_errors();
+        assert!(errors.is_empty(), "{:?}", errors);
+        let layout: Vec<TokenType> = tokens
+            .into_iter()
+            .map(|token| token.token_type)
+            .filter(|t| matches!(t, TokenType::INDENT | TokenType::DEDENT))
+            .collect();
+        assert_eq!(
+            layout,
+            vec![
+                TokenType::INDENT,
+                TokenType::INDENT,
+                TokenType::DEDENT,
+                TokenType::DEDENT
+            ]
+        );
+
+        // lignes de blancs seuls
+        let mut lexer = Lexer::new("if a:\n    x\n  \n    y\n", SyntaxMode::Indentation);
+        let (_, errors) = lexer.tokenize_with_errors();
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_mixed_tabs_and_spaces() {
+        let source = "if a:\n    x\nif b:\n\ty\n";
+        let mut lexer = Lexer::new(source, SyntaxMode::Indentation);
+        let (_, errors) = lexer.tokenize_with_errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, LexerErrorType::MixedTabsAndSpaces);
+    }
+
+    #[test]
+    fn test_configurable_tab_width() {
+        let mut lexer = Lexer::new("\t\tx", SyntaxMode::Indentation).with_tab_width(8);
+        assert_eq!(lexer.count_indentation(), 16);
+    }
+
+    #[test]
+    fn test_floor_division_and_three_char_operators() {
+        let mut lexer = Lexer::new("a // b //= c **= d <<= e >>= f", SyntaxMode::Indentation);
+        let operators: Vec<TokenType> = lexer
+            .tokenize()
+            .into_iter()
+            .map(|token| token.token_type)
+            .filter(|token_type| matches!(token_type, TokenType::OPERATOR(_)))
+            .collect();
+        assert_eq!(
+            operators,
+            vec![
+                TokenType::OPERATOR(Operators::DOUBLESLASH),
+                TokenType::OPERATOR(Operators::DOUBLESLASHEQUAL),
+                TokenType::OPERATOR(Operators::DOUBLESTAREQUAL),
+                TokenType::OPERATOR(Operators::LEFTSHIFTEQUAL),
+                TokenType::OPERATOR(Operators::RIGHTSHIFTEQUAL),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_floor_division_is_a_comment_in_braces_mode() {
+        // '//' et '//=' n'existent qu'en mode indentation ; en mode accolades c'est un commentaire
+        let mut lexer = Lexer::new("a // b //= c", SyntaxMode::Braces);
+        let tokens: Vec<TokenType> = lexer.tokenize().into_iter().map(|token| token.token_type).collect();
+        assert!(matches!(tokens[0], TokenType::IDENTIFIER { .. }));
+        assert!(!tokens.iter().any(|token| matches!(
+            token,
+            TokenType::OPERATOR(Operators::DOUBLESLASH) | TokenType::OPERATOR(Operators::DOUBLESLASHEQUAL)
+        )));
+        assert!(!tokens.iter().any(|token| matches!(token, TokenType::IDENTIFIER { name } if name == "b")));
+    }
+
+    #[test]
+    fn test_colon_equal_operator() {
+        let mut lexer = Lexer::new("(n := 10)", SyntaxMode::Indentation);
+        let tokens = lexer.tokenize();
+        assert_eq!(tokens[2].token_type, TokenType::OPERATOR(Operators::COLONEQUAL));
+        assert_eq!(tokens[2].text, ":=");
+    }
+
     //     #[test]
     //     fn test_indentation_with_empty_lines() {
     //         let source = r#"
@@ -0,0 +1,260 @@
+use std::fs;
+use std::path::PathBuf;
+
+use pyrust::modules::loader::{ModuleError, ModuleLoader, SymbolKind};
+use pyrust::parser::ast::Type;
+use pyrust::parser::inference::TypeContext;
+use pyrust::parser::parser::Parser;
+use pyrust::{Lexer, SyntaxMode};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cree un projet temporaire a partir de (chemin relatif, contenu)
+    fn project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("pyrust_module_test_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for (path, source) in files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, source).unwrap();
+        }
+        root
+    }
+
+    mod loader_tests {
+        use super::*;
+
+        #[test]
+        fn test_resolves_nested_modules() {
+            let root = project("nested", &[
+                ("main.pyr", "use math.ops;\nuse util;\nfn main() { let x = 1; }"),
+                ("math/ops.pyr", "pub fn add(a: int, b: int) -> int { return a + b }"),
+                ("util/mod.pyr", "pub const LIMIT: int = 10;"),
+            ]);
+            let graph = ModuleLoader::new(&root, SyntaxMode::Braces).load("main").unwrap();
+
+            assert_eq!(graph.len(), 3);
+            assert_eq!(graph.get("util").unwrap().path, root.join("util/mod.pyr"));
+            assert_eq!(graph.dependencies("main"), vec!["math.ops", "util"]);
+            let order: Vec<&str> = graph.modules_in_order().map(|module| module.name.as_str()).collect();
+            assert_eq!(order, vec!["math.ops", "util", "main"]);
+        }
+
+        #[test]
+        fn test_search_paths() {
+            let root = project("search_root", &[("main.pyr", "use vendored::{helper};")]);
+            let lib = project("search_lib", &[("vendored.pyr", "pub fn helper() -> int { return 1 }")]);
+
+            assert!(matches!(
+                ModuleLoader::new(&root, SyntaxMode::Braces).load("main"),
+                Err(ModuleError::NotFound { .. })
+            ));
+            let graph = ModuleLoader::new(&root, SyntaxMode::Braces).with_search_path(&lib).load("main").unwrap();
+            assert_eq!(graph.get("vendored").unwrap().path, lib.join("vendored.pyr"));
+        }
+
+        #[test]
+        fn test_modules_are_loaded_once() {
+            let root = project("diamond", &[
+                ("main.pyr", "use left;\nuse right;"),
+                ("left.pyr", "use shared;"),
+                ("right.pyr", "use shared;"),
+                ("shared.pyr", "pub const VALUE: int = 1;"),
+            ]);
+            let graph = ModuleLoader::new(&root, SyntaxMode::Braces).load("main").unwrap();
+
+            assert_eq!(graph.len(), 4);
+            let order: Vec<&str> = graph.modules_in_order().map(|module| module.name.as_str()).collect();
+            assert_eq!(order, vec!["shared", "left", "right", "main"]);
+        }
+
+        #[test]
+        fn test_circular_import() {
+            let root = project("cycle", &[
+                ("main.pyr", "use a;"),
+                ("a.pyr", "use b;"),
+                ("b.pyr", "use c;"),
+                ("c.pyr", "use a;"),
+            ]);
+            let error = ModuleLoader::new(&root, SyntaxMode::Braces).load("main").unwrap_err();
+
+            assert!(matches!(&error, ModuleError::CircularImport { cycle } if cycle == &["a", "b", "c", "a"]));
+            assert_eq!(error.to_string(), "Circular import: a -> b -> c -> a");
+        }
+
+        #[test]
+        fn test_missing_module_and_symbol() {
+            let root = project("missing", &[
+                ("main.pyr", "use shapes::{area, volume};"),
+                ("shapes.pyr", "pub fn area() -> int { return 1 }"),
+                ("other.pyr", "use nowhere;"),
+            ]);
+
+            let error = ModuleLoader::new(&root, SyntaxMode::Braces).load("main").unwrap_err();
+            assert!(matches!(&error, ModuleError::UnknownSymbol { symbol, .. } if symbol == "volume"));
+
+            let error = ModuleLoader::new(&root, SyntaxMode::Braces).load("other").unwrap_err();
+            assert!(matches!(&error, ModuleError::NotFound { module, .. } if module == "nowhere"));
+            assert!(error.to_string().contains("nowhere.pyr"));
+        }
+
+        #[test]
+        fn test_indentation_mode_modules() {
+            let root = project("indentation", &[
+                ("main.pyr", "use shapes::{SIDE as side}\nlet x = 1\n"),
+                ("shapes.pyr", "pub const SIDE: int = 3\n"),
+            ]);
+            let graph = ModuleLoader::new(&root, SyntaxMode::Indentation).load("main").unwrap();
+            assert_eq!(graph.imported_symbols("main")["side"].kind, SymbolKind::Constant);
+        }
+    }
+
+    mod symbol_tests {
+        use super::*;
+
+        #[test]
+        fn test_imported_symbols() {
+            let root = project("symbols", &[
+                ("main.pyr", "use shapes::{area as surface, SIDE};\nuse shapes.perimeter;\nuse util as u;"),
+                ("shapes.pyr", "pub fn area(side: int) -> int { return side * side }\npub fn perimeter(side: int) -> int { return side * 4 }\npub const SIDE: int = 3;"),
+                ("util.pyr", "fn helper() -> int { return 1 }"),
+            ]);
+            let graph = ModuleLoader::new(&root, SyntaxMode::Braces).load("main").unwrap();
+            let symbols = graph.imported_symbols("main");
+
+            assert_eq!(symbols["surface"].name, "area");
+            assert_eq!(symbols["surface"].kind, SymbolKind::Function);
+            assert_eq!(symbols["surface"].module, "shapes");
+            assert_eq!(symbols["SIDE"].kind, SymbolKind::Constant);
+            assert_eq!(symbols["perimeter"].kind, SymbolKind::Function);
+            assert_eq!(symbols["u"].kind, SymbolKind::Module);
+            assert_eq!(symbols["u"].name, "util");
+        }
+
+        #[test]
+        fn test_imports_visible_to_type_checker() {
+            let root = project("typed", &[
+                ("main.pyr", "use shapes::{area as surface, SIDE};"),
+                ("shapes.pyr", "pub fn area(side: int) -> int { return side * side }\npub const SIDE: int = 3;"),
+            ]);
+            let graph = ModuleLoader::new(&root, SyntaxMode::Braces).load("main").unwrap();
+
+            let mut context = TypeContext::new();
+            context.declare_imports(&graph.imported_symbols("main")).unwrap();
+            let mut lexer = Lexer::new("surface(SIDE)", SyntaxMode::Braces);
+            let mut parser = Parser::new(lexer.tokenize(), SyntaxMode::Braces);
+            let call = parser.parse_expression(0).unwrap();
+            assert_eq!(context.infer_expression(&call), Ok(Type::Int));
+        }
+    }
+
+    mod wildcard_tests {
+        use super::*;
+
+        #[test]
+        fn test_from_imports() {
+            let root = project("from_import", &[
+                ("main.pyr", "from shapes import Square as Tile\nfrom consts import *\n"),
+                ("shapes.pyr", "pub struct Square {side: int}\n"),
+                ("consts.pyr", "pub const SIDE: int = 3\nconst HIDDEN: int = 4\n"),
+            ]);
+            let graph = ModuleLoader::new(&root, SyntaxMode::Indentation).load("main").unwrap();
+            let symbols = graph.imported_symbols("main");
+
+            assert_eq!(symbols["Tile"].kind, SymbolKind::Struct);
+            assert_eq!(symbols["SIDE"].module, "consts");
+            assert!(!symbols.contains_key("HIDDEN"));
+        }
+
+        #[test]
+        fn test_wildcard_conflicts() {
+            let root = project("wildcard_conflict", &[
+                ("explicit.pyr", "from a import VALUE;\nfrom b import *;"),
+                ("local.pyr", "from a import *;\nfn VALUE() -> int { return 0 }"),
+                ("same.pyr", "from a import *;\nfrom a import VALUE;"),
+                ("a.pyr", "pub const VALUE: int = 1;"),
+                ("b.pyr", "pub const VALUE: int = 2;"),
+            ]);
+
+            let error = ModuleLoader::new(&root, SyntaxMode::Braces).load("explicit").unwrap_err();
+            assert!(matches!(&error, ModuleError::ImportConflict { name, first, second, .. }
+                if name == "VALUE" && first == "a" && second == "b"));
+            assert_eq!(error.to_string(), "Name 'VALUE' in module 'explicit' comes from both 'a' and 'b'");
+
+            let error = ModuleLoader::new(&root, SyntaxMode::Braces).load("local").unwrap_err();
+            assert!(matches!(&error, ModuleError::ImportConflict { first, .. } if first == "local"));
+
+            assert!(ModuleLoader::new(&root, SyntaxMode::Braces).load("same").is_ok());
+        }
+    }
+
+    mod visibility_tests {
+        use super::*;
+
+        #[test]
+        fn test_private_symbol_import() {
+            let root = project("private_import", &[
+                ("main.pyr", "use shapes::{area, helper};"),
+                ("shapes.pyr", "pub fn area() -> int { return helper() }\nfn helper() -> int { return 1 }"),
+            ]);
+            let error = ModuleLoader::new(&root, SyntaxMode::Braces).load("main").unwrap_err();
+            assert!(matches!(&error, ModuleError::PrivateSymbol { symbol, .. } if symbol == "helper"));
+        }
+
+        #[test]
+        fn test_private_access_across_files() {
+            let root = project("private_access", &[
+                ("main.pyr", "use shapes;\nfn main() { let a = shapes.area(); let b = shapes.helper(); }"),
+                ("ok.pyr", "use shapes;\nuse shapes::{Square};\nfn main(s: Square) { let a = shapes.area(); let b = s.side; }"),
+                ("fields.pyr", "use shapes::{Square};\nfn main(s: Square) { let b = s.cache; }"),
+                ("shapes.pyr", "pub fn area() -> int { return helper() }\nfn helper() -> int { return 1 }\npub struct Square {pub side: int, cache: int}"),
+            ]);
+
+            let graph = ModuleLoader::new(&root, SyntaxMode::Braces).load("main").unwrap();
+            assert!(graph.check_visibility("shapes").is_ok());
+            let error = graph.check_visibility("main").unwrap_err();
+            assert_eq!(error.to_string(), "In module 'main': 'helper' is private to module 'shapes'");
+
+            let graph = ModuleLoader::new(&root, SyntaxMode::Braces).load("ok").unwrap();
+            assert!(graph.check_visibility("ok").is_ok());
+
+            let graph = ModuleLoader::new(&root, SyntaxMode::Braces).load("fields").unwrap();
+            assert!(matches!(graph.check_visibility("fields"), Err(ModuleError::PrivateAccess { .. })));
+        }
+
+        #[test]
+        fn test_private_field_in_struct_literal() {
+            let root = project("private_literal", &[
+                ("main.pyr", "use shapes::{Square};\nfn main() { let s = Square { side: 1, cache: 0 }; }"),
+                ("ok.pyr", "use shapes::{Square};\nfn main(t: Square) { let s = Square { side: 2, ..t }; }"),
+                ("shapes.pyr", "pub struct Square {pub side: int, cache: int}\npub fn unit() -> Square { return Square { side: 1, cache: 0 } }"),
+            ]);
+
+            let graph = ModuleLoader::new(&root, SyntaxMode::Braces).load("main").unwrap();
+            assert!(graph.check_visibility("shapes").is_ok());
+            let error = graph.check_visibility("main").unwrap_err();
+            assert_eq!(error.to_string(), "In module 'main': Member 'cache' of 'Square' is private to module 'shapes'");
+
+            let graph = ModuleLoader::new(&root, SyntaxMode::Braces).load("ok").unwrap();
+            assert!(graph.check_visibility("ok").is_ok());
+        }
+
+        #[test]
+        fn test_private_field_in_struct_pattern() {
+            let root = project("private_pattern", &[
+                ("main.pyr", "use shapes::{Square};\nfn main(s: Square) { if let Square { cache, .. } = s { print(cache); } }"),
+                ("ok.pyr", "use shapes::{Square};\nfn main(s: Square) { if let Square { side, .. } = s { print(side); } }"),
+                ("shapes.pyr", "pub struct Square {pub side: int, cache: int}"),
+            ]);
+
+            let graph = ModuleLoader::new(&root, SyntaxMode::Braces).load("main").unwrap();
+            let error = graph.check_visibility("main").unwrap_err();
+            assert_eq!(error.to_string(), "In module 'main': Member 'cache' of 'Square' is private to module 'shapes'");
+
+            let graph = ModuleLoader::new(&root, SyntaxMode::Braces).load("ok").unwrap();
+            assert!(graph.check_visibility("ok").is_ok());
+        }
+    }
+}
@@ -0,0 +1,447 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use num_bigint::BigInt;
+
+use pyrust::parser::ast::{ASTNode, Declaration, Expression, LambdaExpression, Statement, Type, Visibility};
+use pyrust::parser::parser::Parser;
+use pyrust::runtime::cast::{cast_value, ScalarValue};
+use pyrust::runtime::closure::{Closure, Environment};
+use pyrust::runtime::executor::Executor;
+use pyrust::runtime::generator::{Generator, Resume};
+use pyrust::runtime::membership::{is, is_in, is_not, not_in};
+use pyrust::runtime::slice::{slice, slice_str, SliceBounds};
+use pyrust::runtime::try_op::branch;
+use pyrust::semantic::cast::ScalarType;
+use pyrust::semantic::closures::resolve_captures;
+use pyrust::{Lexer, SyntaxMode};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod closure_tests {
+        use super::*;
+
+        #[derive(Clone)]
+        enum Value {
+            Int(i64),
+            Closure(Closure<Value>),
+        }
+
+        impl Value {
+            fn int(&self) -> i64 {
+                match self {
+                    Value::Int(n) => *n,
+                    Value::Closure(_) => panic!("Expected an int"),
+                }
+            }
+        }
+
+        fn int(environment: &Environment<Value>, name: &str) -> i64 {
+            environment.get(name).expect("undefined variable").int()
+        }
+
+        // fermeture renvoyee par la fonction `source`, apres analyse des captures
+        fn returned_lambda(source: &str) -> LambdaExpression {
+            let mut lexer = Lexer::new(source, SyntaxMode::Braces);
+            let mut parser = Parser::new(lexer.tokenize(), SyntaxMode::Braces);
+            let Ok(ASTNode::Declaration(Declaration::Function(mut function))) = parser.parse_function_declaration(Visibility::Private) else {
+                panic!("Expected a function declaration");
+            };
+            resolve_captures(&mut function).expect("capture error");
+            let Some(ASTNode::Statement(Statement::ReturnStatement(ret))) = function.body.pop() else {
+                panic!("Expected a return statement");
+            };
+            let Some(Expression::LambdaExpression(lambda)) = ret.value else { panic!("Expected a closure") };
+            lambda
+        }
+
+        #[test]
+        fn test_reference_captures_share_the_binding() {
+            let lambda = returned_lambda("fn f(n: int) { return |x| x + n }");
+            let mut environment = Environment::new();
+            environment.define("n", Value::Int(1));
+            let add = Closure::capture(&lambda, &environment, |frame| Ok(Value::Int(int(frame, "x") + int(frame, "n")))).unwrap();
+
+            assert_eq!(add.call(vec![Value::Int(1)]).unwrap().int(), 2);
+            environment.set("n", Value::Int(10)).unwrap();
+            assert_eq!(add.call(vec![Value::Int(1)]).unwrap().int(), 11);
+
+            // une capture par reference ne peut pas etre affectee depuis la fermeture
+            let write = Closure::capture(&lambda, &environment, |frame| {
+                frame.set("n", Value::Int(0))?;
+                Ok(Value::Int(0))
+            })
+            .unwrap();
+            assert_eq!(write.call(vec![Value::Int(0)]).err().unwrap(), "Cannot assign to 'n': it is captured by reference");
+        }
+
+        #[test]
+        fn test_mutable_captures_update_the_enclosing_scope() {
+            let lambda = returned_lambda("fn f() { let mut count = 0; return || count += 1 }");
+            let mut environment = Environment::new();
+            environment.define("count", Value::Int(0));
+            let increment = Closure::capture(&lambda, &environment, |frame| {
+                let count = int(frame, "count") + 1;
+                frame.set("count", Value::Int(count))?;
+                Ok(Value::Int(count))
+            })
+            .unwrap();
+
+            increment.call(vec![]).unwrap();
+            increment.call(vec![]).unwrap();
+            assert_eq!(int(&environment, "count"), 2);
+        }
+
+        #[test]
+        fn test_move_captures_copy_the_value() {
+            let lambda = returned_lambda("fn f() { let mut total = 0; return move |x| total += x }");
+            let mut environment = Environment::new();
+            environment.define("total", Value::Int(5));
+            let accumulate = Closure::capture(&lambda, &environment, |frame| {
+                let total = int(frame, "total") + int(frame, "x");
+                frame.set("total", Value::Int(total))?;
+                Ok(Value::Int(total))
+            })
+            .unwrap();
+
+            assert_eq!(accumulate.call(vec![Value::Int(1)]).unwrap().int(), 6);
+            assert_eq!(accumulate.call(vec![Value::Int(1)]).unwrap().int(), 7);
+            environment.set("total", Value::Int(100)).unwrap();
+            assert_eq!(accumulate.call(vec![Value::Int(1)]).unwrap().int(), 8);
+            assert_eq!(int(&environment, "total"), 100);
+        }
+
+        #[test]
+        fn test_closures_are_stored_passed_and_returned() {
+            // `make_adder(n)` renvoie une fermeture qui survit a la portee de `n`
+            let lambda = returned_lambda("fn make_adder(n: int) { return move |x| x + n }");
+            let make_adder = |n: i64| {
+                let mut scope = Environment::new();
+                scope.define("n", Value::Int(n));
+                Closure::capture(&lambda, &scope, |frame| Ok(Value::Int(int(frame, "x") + int(frame, "n")))).unwrap()
+            };
+            let mut environment = Environment::new();
+            environment.define("add3", Value::Closure(make_adder(3)));
+
+            // `apply(g, x)` recoit la fermeture en argument
+            let apply = |g: &Value, x: i64| match g {
+                Value::Closure(closure) => closure.call(vec![Value::Int(x)]),
+                Value::Int(_) => Err("Expected a closure".to_string()),
+            };
+            let add3 = environment.get("add3").unwrap();
+            assert_eq!(apply(&add3, 4).unwrap().int(), 7);
+
+            let Value::Closure(closure) = add3 else { unreachable!() };
+            assert_eq!(closure.arity(), 1);
+            assert_eq!(closure.call(vec![]).err().unwrap(), "Closure expects 1 arguments, got 0");
+        }
+
+        #[test]
+        fn test_undefined_capture() {
+            let lambda = returned_lambda("fn f(n: int) { return |x| x + n }");
+            let error = Closure::capture(&lambda, &Environment::new(), |_| Ok(Value::Int(0))).err().unwrap();
+            assert_eq!(error, "Captured variable 'n' is not defined");
+        }
+    }
+
+    mod executor_tests {
+        use super::*;
+
+        #[test]
+        fn test_block_on_returns_output() {
+            let executor = Executor::new();
+            assert_eq!(executor.block_on(async { 40 + 2 }), Ok(42));
+        }
+
+        #[test]
+        fn test_spawned_tasks_are_joined() {
+            let executor = Executor::new();
+            let handle = executor.handle();
+            let result = executor.block_on(async move {
+                let first = handle.spawn(async { 1 });
+                let second = handle.spawn(async { 2 });
+                first.await + second.await
+            });
+            assert_eq!(result, Ok(3));
+        }
+
+        #[test]
+        fn test_timers_use_virtual_time_in_deadline_order() {
+            let executor = Executor::new();
+            let handle = executor.handle();
+            let log = Rc::new(RefCell::new(Vec::new()));
+
+            let task_log = log.clone();
+            let result = executor.block_on(async move {
+                let mut tasks = Vec::new();
+                for (name, delay) in [("slow", 30), ("fast", 10), ("medium", 20), ("fast2", 10)] {
+                    let task_handle = handle.clone();
+                    let task_log = task_log.clone();
+                    tasks.push(handle.spawn(async move {
+                        task_handle.sleep(Duration::from_millis(delay)).await;
+                        task_log.borrow_mut().push((name, task_handle.now()));
+                    }));
+                }
+                for task in tasks {
+                    task.await;
+                }
+                handle.now()
+            });
+
+            // les timers de meme echeance se declenchent dans leur ordre de creation
+            assert_eq!(result, Ok(Duration::from_millis(30)));
+            assert_eq!(
+                *log.borrow(),
+                vec![
+                    ("fast", Duration::from_millis(10)),
+                    ("fast2", Duration::from_millis(10)),
+                    ("medium", Duration::from_millis(20)),
+                    ("slow", Duration::from_millis(30)),
+                ]
+            );
+        }
+
+        #[test]
+        fn test_deadlock_is_reported() {
+            let executor = Executor::new();
+            let result = executor.block_on(std::future::pending::<()>());
+            assert!(result.is_err());
+        }
+    }
+
+    mod generator_tests {
+        use super::*;
+
+        fn count(n: i64) -> Generator<i64> {
+            let mut i = 0;
+            Generator::new(move || {
+                if i < n {
+                    i += 1;
+                    Resume::Yield(i - 1)
+                } else {
+                    Resume::Return
+                }
+            })
+        }
+
+        #[test]
+        fn test_generator_resumes_until_return() {
+            let mut generator = count(3);
+            assert_eq!(generator.next(), Some(0));
+            assert!(!generator.is_finished());
+            assert_eq!(generator.by_ref().collect::<Vec<_>>(), vec![1, 2]);
+            assert!(generator.is_finished());
+            assert_eq!(generator.next(), None);
+        }
+
+        #[test]
+        fn test_yield_from_delegates() {
+            let mut step = 0;
+            let generator = Generator::new(move || {
+                step += 1;
+                match step {
+                    1 => Resume::Yield(-1),
+                    2 => Resume::YieldFrom(Box::new(count(2))),
+                    3 => Resume::Yield(10),
+                    _ => Resume::Return,
+                }
+            });
+            assert_eq!(generator.collect::<Vec<_>>(), vec![-1, 0, 1, 10]);
+        }
+    }
+
+    mod slice_tests {
+        use super::*;
+        use std::borrow::Cow;
+
+        const ITEMS: [i32; 6] = [0, 1, 2, 3, 4, 5];
+
+        fn sliced(start: Option<i64>, end: Option<i64>, step: Option<i64>) -> Vec<i32> {
+            slice(&ITEMS, &SliceBounds::new(start, end, step)).unwrap().into_owned()
+        }
+
+        #[test]
+        fn test_python_slices() {
+            assert_eq!(sliced(Some(1), Some(5), Some(2)), vec![1, 3]);
+            assert_eq!(sliced(None, Some(3), None), vec![0, 1, 2]);
+            assert_eq!(sliced(Some(-2), None, None), vec![4, 5]);
+            assert_eq!(sliced(None, None, Some(-1)), vec![5, 4, 3, 2, 1, 0]);
+            assert_eq!(sliced(Some(4), Some(1), Some(-2)), vec![4, 2]);
+            assert_eq!(sliced(Some(-100), Some(100), None), ITEMS.to_vec());
+            assert_eq!(sliced(Some(4), Some(2), None), Vec::<i32>::new());
+        }
+
+        #[test]
+        fn test_inclusive_slices() {
+            let bounds = SliceBounds::inclusive_range(Some(2), 4);
+            assert_eq!(slice(&ITEMS, &bounds).unwrap().into_owned(), vec![2, 3, 4]);
+            let bounds = SliceBounds::inclusive_range(None, -1);
+            assert_eq!(slice(&ITEMS, &bounds).unwrap().len(), 6);
+        }
+
+        #[test]
+        fn test_views_and_copies() {
+            assert!(matches!(slice(&ITEMS, &SliceBounds::new(Some(1), Some(3), None)).unwrap(), Cow::Borrowed(_)));
+            assert!(matches!(slice(&ITEMS, &SliceBounds::new(None, None, Some(2))).unwrap(), Cow::Owned(_)));
+            assert!(slice(&ITEMS, &SliceBounds::new(None, None, Some(0))).is_err());
+        }
+
+        #[test]
+        fn test_string_slices() {
+            assert_eq!(slice_str("héllo", &SliceBounds::new(Some(1), Some(3), None)).unwrap(), "él");
+            assert_eq!(slice_str("héllo", &SliceBounds::new(None, None, Some(-1))).unwrap(), "olléh");
+            assert_eq!(slice_str("héllo", &SliceBounds::new(Some(-3), None, None)).unwrap(), "llo");
+        }
+    }
+
+    mod try_tests {
+        use super::*;
+
+        // `fn first_plus(values, n) -> Option<int> { return Some(values.first()? + n) }`
+        fn first_plus(values: &[i64], n: i64) -> Option<i64> {
+            let first = match branch(values.first().copied()) {
+                Ok(value) => value,
+                Err(early_return) => return early_return,
+            };
+            Some(first + n)
+        }
+
+        // `fn parse_sum(a, b) -> Result<int, str> { return Ok(parse(a)? + parse(b)?) }`
+        fn parse_sum(a: &str, b: &str) -> Result<i64, String> {
+            let parse = |text: &str| text.parse::<i64>().map_err(|_| format!("invalid number '{}'", text));
+            let a = match branch(parse(a)) {
+                Ok(value) => value,
+                Err(early_return) => return early_return,
+            };
+            let b = match branch(parse(b)) {
+                Ok(value) => value,
+                Err(early_return) => return early_return,
+            };
+            Ok(a + b)
+        }
+
+        #[test]
+        fn test_option_early_return() {
+            assert_eq!(first_plus(&[2, 3], 1), Some(3));
+            assert_eq!(first_plus(&[], 1), None);
+        }
+
+        #[test]
+        fn test_result_early_return() {
+            assert_eq!(parse_sum("1", "2"), Ok(3));
+            assert_eq!(parse_sum("x", "2"), Err("invalid number 'x'".to_string()));
+            assert_eq!(parse_sum("1", "y"), Err("invalid number 'y'".to_string()));
+        }
+
+        #[test]
+        fn test_early_return_keeps_the_function_type() {
+            // `Some(1)?` et `None?` dans une fonction renvoyant `Option<str>`
+            assert_eq!(branch::<_, Option<String>>(Some(1)), Ok(1));
+            assert_eq!(branch::<Option<i64>, Option<String>>(None), Err(None));
+            assert_eq!(branch::<Result<i64, String>, Result<bool, String>>(Err("e".to_string())), Err(Err("e".to_string())));
+        }
+    }
+
+    mod membership_tests {
+        use super::*;
+        use std::collections::{HashMap, HashSet};
+
+        #[test]
+        fn test_membership_in_collections() {
+            assert!(is_in(&2, &[1, 2, 3][..]));
+            assert!(not_in(&4, &vec![1, 2, 3]));
+            assert!(is_in(&"b", &HashSet::from(["a", "b"])));
+            assert!(is_in(&9, &(0..10)));
+            assert!(not_in(&10, &(0..10)));
+            assert!(is_in(&10, &(0..=10)));
+        }
+
+        #[test]
+        fn test_membership_in_dict_keys() {
+            let ages = HashMap::from([("ada", 36)]);
+            assert!(is_in(&"ada", &ages));
+            assert!(not_in(&"bob", &ages));
+        }
+
+        #[test]
+        fn test_membership_in_strings() {
+            assert!(is_in("ell", "hello"));
+            assert!(is_in(&'h', "hello"));
+            assert!(not_in(&'z', "hello"));
+        }
+
+        #[test]
+        fn test_identity_compares_references() {
+            let list = Rc::new(vec![1, 2]);
+            let alias = list.clone();
+            let copy = Rc::new(vec![1, 2]);
+            assert!(is(&list, &alias));
+            assert!(is_not(&list, &copy));
+            assert_eq!(list, copy);
+        }
+    }
+
+    mod cast_tests {
+        use super::*;
+
+        #[test]
+        fn test_integer_truncation() {
+            let u8_type = ScalarType::from_type(&Type::Named("u8".to_string())).unwrap();
+            let i8_type = ScalarType::from_type(&Type::Named("i8".to_string())).unwrap();
+
+            assert_eq!(
+                cast_value(&ScalarValue::Int(BigInt::from(300)), u8_type),
+                Ok(ScalarValue::Int(BigInt::from(44)))
+            );
+            assert_eq!(
+                cast_value(&ScalarValue::Int(BigInt::from(-1)), u8_type),
+                Ok(ScalarValue::Int(BigInt::from(255)))
+            );
+            assert_eq!(
+                cast_value(&ScalarValue::Int(BigInt::from(200)), i8_type),
+                Ok(ScalarValue::Int(BigInt::from(-56)))
+            );
+        }
+
+        #[test]
+        fn test_float_to_int_saturation() {
+            let u8_type = ScalarType::from_type(&Type::Named("u8".to_string())).unwrap();
+
+            assert_eq!(
+                cast_value(&ScalarValue::Float(-3.9), ScalarType::INT),
+                Ok(ScalarValue::Int(BigInt::from(-3)))
+            );
+            assert_eq!(
+                cast_value(&ScalarValue::Float(1e10), u8_type),
+                Ok(ScalarValue::Int(BigInt::from(255)))
+            );
+            assert_eq!(
+                cast_value(&ScalarValue::Float(-5.0), u8_type),
+                Ok(ScalarValue::Int(BigInt::from(0)))
+            );
+            assert_eq!(
+                cast_value(&ScalarValue::Float(f64::NAN), ScalarType::INT),
+                Ok(ScalarValue::Int(BigInt::from(0)))
+            );
+        }
+
+        #[test]
+        fn test_char_and_bool_conversions() {
+            assert_eq!(
+                cast_value(&ScalarValue::Char('A'), ScalarType::INT),
+                Ok(ScalarValue::Int(BigInt::from(65)))
+            );
+            assert_eq!(
+                cast_value(&ScalarValue::Bool(true), ScalarType::INT),
+                Ok(ScalarValue::Int(BigInt::from(1)))
+            );
+            assert_eq!(
+                cast_value(&ScalarValue::Int(BigInt::from(97)), ScalarType::Char),
+                Ok(ScalarValue::Char('a'))
+            );
+        }
+    }
+}
@@ -0,0 +1,636 @@
+use pyrust::parser::ast::{ASTNode, Declaration, Expression, FunctionDeclaration, Type, Visibility};
+use pyrust::parser::inference::TypeContext;
+use pyrust::parser::parser::Parser;
+use pyrust::{Lexer, SyntaxMode};
+use pyrust::semantic::builtins::{check_try, future_type, iterator_type, option_type, result_type};
+use pyrust::semantic::cast::check_cast;
+use pyrust::semantic::closures::resolve_captures;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Fonction d'aide pour parser une expression
+    fn parse(source: &str) -> Expression {
+        let mut lexer = Lexer::new(source, SyntaxMode::Braces);
+        let mut parser = Parser::new(lexer.tokenize(), SyntaxMode::Braces);
+        parser.parse_expression(0).expect("parse error")
+    }
+
+    // Fonction d'aide pour inferer le type d'une expression
+    fn infer(source: &str) -> Result<Type, String> {
+        TypeContext::new().infer_expression(&parse(source))
+    }
+
+    // Fonction d'aide pour parser une declaration de fonction
+    fn parse_function(source: &str) -> FunctionDeclaration {
+        let mut lexer = Lexer::new(source, SyntaxMode::Braces);
+        let mut parser = Parser::new(lexer.tokenize(), SyntaxMode::Braces);
+        match parser.parse_function_declaration(Visibility::Private).expect("parse error") {
+            ASTNode::Declaration(Declaration::Function(function)) => function,
+            _ => panic!("Expected a function declaration"),
+        }
+    }
+
+    // Fonction d'aide pour verifier le corps d'une fonction
+    fn check_function(source: &str) -> Result<(), String> {
+        TypeContext::new().check_function(&parse_function(source))
+    }
+
+    // Fonction d'aide pour appliquer a une fonction les passes qui reecrivent l'AST (captures, indexation)
+    fn resolve(source: &str) -> Result<FunctionDeclaration, String> {
+        let mut function = parse_function(source);
+        resolve_captures(&mut function)?;
+        TypeContext::new().resolve_indexing(&mut function)?;
+        Ok(function)
+    }
+
+    // Fonction d'aide pour parser toutes les instructions d'un fichier
+    fn parse_file(source: &str) -> Vec<ASTNode> {
+        let mut lexer = Lexer::new(source, SyntaxMode::Braces);
+        let mut parser = Parser::new(lexer.tokenize(), SyntaxMode::Braces);
+        let mut body = Vec::new();
+        while !parser.is_at_end() {
+            body.push(parser.parse_statement().expect("parse error"));
+        }
+        body
+    }
+
+    // Fonction d'aide pour verifier les fonctions d'un fichier apres avoir declare ses structures et fonctions
+    fn check_file(source: &str) -> Result<TypeContext, String> {
+        let mut context = TypeContext::new();
+        let mut functions = Vec::new();
+        for node in parse_file(source) {
+            match node {
+                ASTNode::Declaration(Declaration::Structure(structure)) => context.declare_struct(&structure),
+                ASTNode::Declaration(Declaration::Function(function)) => {
+                    context.declare_function(&function);
+                    functions.push(function);
+                }
+                _ => {}
+            }
+        }
+        functions.iter().try_for_each(|function| context.check_function(function))?;
+        Ok(context)
+    }
+
+    mod cast_tests {
+        use super::*;
+
+        #[test]
+        fn test_allowed_casts() {
+            assert_eq!(check_cast(&Type::Int, &Type::Float), Ok(Type::Float));
+            assert_eq!(check_cast(&Type::Float, &Type::Int), Ok(Type::Int));
+            assert_eq!(check_cast(&Type::Bool, &Type::Int), Ok(Type::Int));
+            assert_eq!(check_cast(&Type::Char, &Type::Int), Ok(Type::Int));
+            assert!(check_cast(&Type::Int, &Type::Named("u8".to_string())).is_ok());
+            assert!(check_cast(&Type::Named("u8".to_string()), &Type::Char).is_ok());
+        }
+
+        #[test]
+        fn test_forbidden_casts() {
+            assert!(check_cast(&Type::Int, &Type::Bool).is_err());
+            assert!(check_cast(&Type::Int, &Type::Char).is_err());
+            assert!(check_cast(&Type::Float, &Type::Char).is_err());
+            assert!(check_cast(&Type::String, &Type::Int).is_err());
+            assert!(check_cast(&Type::Int, &Type::Named("Point".to_string())).is_err());
+        }
+    }
+
+    mod conditional_tests {
+        use super::*;
+
+        #[test]
+        fn test_conditional_branches_unify() {
+            assert_eq!(infer("1 if true else 2"), Ok(Type::Int));
+            assert_eq!(infer("if true { 1.5 } else { 2.5 }"), Ok(Type::Float));
+            assert_eq!(infer("if false { 1 } else if true { 2 } else { 3 }"), Ok(Type::Int));
+        }
+
+        #[test]
+        fn test_conditional_branch_mismatch() {
+            assert!(infer("1 if true else 2.5").is_err());
+            assert!(infer("if true { 1 } else { false }").is_err());
+        }
+
+        #[test]
+        fn test_conditional_requires_bool_condition() {
+            assert!(infer("1 if 3 else 2").is_err());
+        }
+
+        #[test]
+        fn test_block_branches_with_locals() {
+            assert!(check_function("fn f(c: bool) -> int { let x = if c { 1 } else { let t = 1; t + 1 }; return x }").is_ok());
+            assert!(check_function("fn f(c: bool) -> int { let x = if c { 1 } else { let t = \"a\"; t }; return x }").is_err());
+            // `t` n'existe plus apres le bloc
+            assert!(check_function("fn f(c: bool) -> int { let x = if c { 1 } else { let t = 1; t }; return t }").is_err());
+        }
+    }
+
+    mod membership_tests {
+        use super::*;
+
+        #[test]
+        fn test_membership_types() {
+            assert_eq!(infer("'a' in \"abc\""), Ok(Type::Bool));
+            assert_eq!(infer("\"ab\" not in \"abc\""), Ok(Type::Bool));
+            assert!(infer("1 in \"abc\"").is_err());
+            assert!(infer("1 in 2").is_err());
+        }
+
+        #[test]
+        fn test_membership_on_user_types() {
+            let source = "struct Bag { items: Array<int> } fn f(bag: Bag) -> bool { return 1 in bag }";
+            assert_eq!(check_file(source).err().unwrap(), "Type 'Bag' does not support membership tests");
+            assert!(check_file("fn f(items: Array<int>) -> bool { return 1 in items }").is_ok());
+            // le parcours d'un type utilisateur reste permis
+            assert!(check_file("struct Bag { items: Array<int> } fn f(bag: Bag) { for x in bag { x } }").is_ok());
+        }
+
+        #[test]
+        fn test_logical_and_identity_types() {
+            assert_eq!(infer("true and not false"), Ok(Type::Bool));
+            assert_eq!(infer("1 is 2"), Ok(Type::Bool));
+            assert!(infer("1 or true").is_err());
+        }
+    }
+    mod comparison_tests {
+        use super::*;
+
+        #[test]
+        fn test_chained_comparison_types() {
+            assert_eq!(infer("0 <= 1 < 10"), Ok(Type::Bool));
+            assert_eq!(infer("1 < 2.5 <= 3"), Ok(Type::Bool));
+            assert_eq!(infer("'a' in \"abc\" == \"abc\""), Ok(Type::Bool));
+        }
+
+        #[test]
+        fn test_chained_comparison_checks_each_link() {
+            assert!(infer("0 <= 1 < \"ten\"").is_err());
+            assert!(infer("true == 1 < 2").is_err());
+            // 'a' in s == true vaut 'a' in s and s == true
+            assert!(infer("'a' in \"abc\" == true").is_err());
+        }
+    }
+
+    mod try_tests {
+        use super::*;
+
+        #[test]
+        fn test_builtin_constructors() {
+            assert_eq!(infer("Some(1)"), Ok(option_type(Type::Int)));
+            assert_eq!(infer("None"), Ok(option_type(Type::Infer)));
+            assert_eq!(infer("Ok(1.5)"), Ok(result_type(Type::Float, Type::Infer)));
+            assert_eq!(infer("Err(\"boom\")"), Ok(result_type(Type::Infer, Type::String)));
+        }
+
+        #[test]
+        fn test_check_try() {
+            let option = option_type(Type::Int);
+            let result = result_type(Type::Int, Type::String);
+            assert_eq!(check_try(&option, Some(&option_type(Type::Bool))), Ok(Type::Int));
+            assert_eq!(check_try(&result, Some(&result_type(Type::Float, Type::String))), Ok(Type::Int));
+            assert!(check_try(&option, Some(&result)).is_err());
+            assert!(check_try(&result, Some(&result_type(Type::Int, Type::Int))).is_err());
+            assert!(check_try(&Type::Int, Some(&option)).is_err());
+            assert!(check_try(&option, None).is_err());
+            assert!(check_try(&option, Some(&Type::Infer)).is_err());
+        }
+
+        #[test]
+        fn test_try_in_function() {
+            assert!(check_function("fn first(x: Option<int>) -> Option<int> { let y = x?; return Some(y + 1) }").is_ok());
+            assert!(check_function("fn parse(x: Result<int, str>) -> Result<float, str> { let y = x? as float; return Ok(y) }").is_ok());
+            assert!(check_function("fn nested(x: Option<Result<int, str>>) -> Result<int, str> { return Ok(1) }").is_ok());
+        }
+
+        #[test]
+        fn test_try_in_incompatible_function() {
+            assert!(check_function("fn first(x: Option<int>) -> int { return x? }").is_err());
+            assert!(check_function("fn parse(x: Result<int, str>) -> Option<int> { let y = x?; return Some(y) }").is_err());
+            assert!(check_function("fn parse(x: Result<int, str>) -> Result<int, str> { return Some(x?) }").is_err());
+            // sans type de retour declare
+            assert_eq!(
+                check_function("fn first(x: Option<int>) { let y = x?; }"),
+                Err("The '?' operator requires the function to declare an Option or Result return type".to_string())
+            );
+        }
+    }
+
+    mod walrus_tests {
+        use super::*;
+
+        #[test]
+        fn test_walrus_binds_in_enclosing_scope() {
+            let mut context = TypeContext::new();
+            assert_eq!(context.infer_expression(&parse("(n := 10) > 3")), Ok(Type::Bool));
+            assert_eq!(context.infer_expression(&parse("n + 1")), Ok(Type::Int));
+        }
+
+        #[test]
+        fn test_walrus_escapes_comprehension() {
+            let mut context = TypeContext::new();
+            let comprehension = parse("[c for c in \"abc\" if (last := c) != \"b\"]");
+            assert_eq!(
+                context.infer_expression(&comprehension),
+                Ok(Type::Array(Box::new(Type::String)))
+            );
+            // la variable de ':=' reste visible, pas la variable d'iteration
+            assert_eq!(context.infer_expression(&parse("last")), Ok(Type::String));
+            assert!(context.infer_expression(&parse("c")).is_err());
+        }
+
+        #[test]
+        fn test_walrus_cannot_rebind_iteration_variable() {
+            assert!(infer("[x for x in \"abc\" if (x := \"z\") != \"a\"]").is_err());
+        }
+    }
+
+    mod async_tests {
+        use super::*;
+
+        #[test]
+        fn test_await_in_async_function() {
+            assert!(check_function("async fn load(url: str) -> str { return url }").is_ok());
+            assert!(check_function("async fn twice(x: Future<int>) -> int { let y = await x; return y * 2 }").is_ok());
+        }
+
+        #[test]
+        fn test_await_outside_async_function() {
+            assert!(check_function("fn twice(x: Future<int>) -> int { return await x }").is_err());
+            assert!(check_function("async fn twice(x: int) -> int { return await x }").is_err());
+        }
+
+        #[test]
+        fn test_async_call_returns_future() {
+            let mut context = check_file("async fn load() -> int { return 1 }").unwrap();
+            assert_eq!(context.infer_expression(&parse("load()")), Ok(future_type(Type::Int)));
+        }
+    }
+
+    mod generator_tests {
+        use super::*;
+
+        #[test]
+        fn test_generator_call_returns_iterator() {
+            let mut context = check_file("fn pair() { yield 1; yield 2; }").unwrap();
+            assert_eq!(context.infer_expression(&parse("pair()")), Ok(iterator_type(Type::Int)));
+        }
+
+        #[test]
+        fn test_generator_in_for_loop() {
+            assert!(check_file(
+                "fn count(n: int) -> Iterator<int> { let i = 0; while i < n { yield i; } } \
+                 fn total() -> int { for x in count(3) { let y = x + 1; } return 0 }"
+            ).is_ok());
+            assert!(check_file(
+                "fn names() { yield \"a\"; } \
+                 fn total() -> int { for x in names() { let y: int = x; } return 0 }"
+            ).is_err());
+        }
+
+        #[test]
+        fn test_yield_from() {
+            assert!(check_file("fn chain(a: Iterator<int>, b: Array<int>) { yield from a; yield from b; }").is_ok());
+            assert!(check_file("fn chain(a: Iterator<int>) { yield from a; yield \"x\"; }").is_err());
+        }
+
+        #[test]
+        fn test_invalid_generators() {
+            assert!(check_file("fn gen() -> int { yield 1; }").is_err());
+            assert!(check_file("fn gen() { yield 1; return 2 }").is_err());
+            assert!(check_file("async fn gen() { yield 1; }").is_err());
+        }
+    }
+
+    mod visibility_tests {
+        use super::*;
+        use pyrust::semantic::visibility::check_visibility;
+
+        const GEOMETRY: &str = "mod geometry { pub fn area(side: int) -> int { return side * helper(side) } fn helper(x: int) -> int { return x } const SCALE: int = 2; pub const UNIT: int = 1; pub struct Square {pub side: int, cache: int} pub mod shapes { pub fn scaled(x: int) -> int { return x * SCALE } } mod internal { pub fn secret() -> int { return 0 } } }";
+
+        #[test]
+        fn test_public_items_are_accessible() {
+            assert!(check_visibility(&parse_file(&format!("{} fn main() {{ let a = geometry.area(2); let b = geometry.UNIT; let c = geometry.shapes.scaled(3); }}", GEOMETRY))).is_ok());
+        }
+
+        #[test]
+        fn test_private_items_are_rejected() {
+            let error = check_visibility(&parse_file(&format!("{} fn main() {{ let a = geometry.helper(2); }}", GEOMETRY))).unwrap_err();
+            assert_eq!(error, "'helper' is private to module 'main.geometry'");
+            assert!(check_visibility(&parse_file(&format!("{} fn main() {{ let a = geometry.SCALE; }}", GEOMETRY))).is_err());
+            assert!(check_visibility(&parse_file(&format!("{} fn main() {{ let a = geometry.internal.secret(); }}", GEOMETRY))).is_err());
+        }
+
+        #[test]
+        fn test_private_fields() {
+            assert!(check_visibility(&parse_file(&format!("{} fn main(s: Square) {{ let a = s.side; }}", GEOMETRY))).is_ok());
+            let error = check_visibility(&parse_file(&format!("{} fn main() {{ let s = geometry.Square(1, 2); let a = s.cache; }}", GEOMETRY))).unwrap_err();
+            assert_eq!(error, "Member 'cache' of 'Square' is private to module 'main.geometry'");
+        }
+
+        #[test]
+        fn test_private_items_visible_in_submodules() {
+            assert!(check_visibility(&parse_file("mod outer { const LIMIT: int = 3; mod inner { pub fn get() -> int { return LIMIT } } fn call() -> int { return inner.get() } }")).is_ok());
+            assert!(check_visibility(&parse_file("mod outer { mod inner { pub fn get() -> int { return 1 } } } fn call() -> int { return outer.inner.get() }")).is_err());
+            assert!(check_visibility(&parse_file("mod outer { mod inner { fn get() -> int { return 1 } } fn call() -> int { return inner.get() } }")).is_err());
+        }
+    }
+
+    mod indexing_tests {
+        use super::*;
+        use pyrust::parser::ast::Statement;
+        use pyrust::semantic::indexing::{index_kind, IndexKind};
+
+        fn returned(function: &FunctionDeclaration) -> &Expression {
+            match function.body.last() {
+                Some(ASTNode::Statement(Statement::ReturnStatement(ret))) => ret.value.as_ref().unwrap(),
+                other => panic!("expected a return, got {:?}", other),
+            }
+        }
+
+        #[test]
+        fn test_index_kind() {
+            let dict = pyrust::parser::ast::Type::Generic(pyrust::parser::ast::GenericType {
+                base: "Dict".to_string(),
+                type_parameters: vec![Type::String, Type::Int],
+            });
+            assert_eq!(index_kind(&dict, &Type::String), Ok(IndexKind::Mapping(Type::Int)));
+            assert!(index_kind(&dict, &Type::Int).is_err());
+            assert_eq!(index_kind(&Type::Array(Box::new(Type::Float)), &Type::Int), Ok(IndexKind::Sequence(Type::Float)));
+            assert_eq!(index_kind(&Type::String, &Type::Int), Ok(IndexKind::Sequence(Type::Char)));
+            assert_eq!(index_kind(&Type::Named("Grid".to_string()), &Type::Int), Ok(IndexKind::Custom));
+            assert_eq!(index_kind(&Type::Infer, &Type::String), Ok(IndexKind::Unresolved));
+            assert!(index_kind(&Type::Int, &Type::Int).is_err());
+        }
+
+        #[test]
+        fn test_dict_access_with_variable_key() {
+            let function = resolve("fn get(d: Dict<str, int>, key: str) -> int { return d[key] }").unwrap();
+            assert!(matches!(returned(&function), Expression::DictAccess(_)));
+            assert!(TypeContext::new().check_function(&function).is_ok());
+        }
+
+        #[test]
+        fn test_array_access() {
+            let function = resolve("fn first() -> int { let a = [1, 2, 3]; let i = 0; return a[i] }").unwrap();
+            assert!(matches!(returned(&function), Expression::IndexAccess(_)));
+
+            let function = resolve("fn nested(grid: Vec<Vec<float>>) -> float { return grid[0][1] }").unwrap();
+            assert!(matches!(returned(&function), Expression::IndexAccess(access) if matches!(*access.array, Expression::IndexAccess(_))));
+            assert!(TypeContext::new().check_function(&function).is_ok());
+        }
+
+        #[test]
+        fn test_access_inside_nested_bodies() {
+            // fermeture
+            let function = resolve("fn getter(d: Dict<str, int>, key: str) -> fn() -> int { return || d[key] }").unwrap();
+            let Expression::LambdaExpression(lambda) = returned(&function) else { panic!("Expected a closure") };
+            assert!(matches!(lambda.body[..], [ASTNode::Expression(Expression::DictAccess(_))]));
+
+            // comprehension : `k` prend le type des elements de `keys`
+            let function = resolve("fn values(d: Dict<str, int>, keys: Array<str>) -> Array<int> { return [d[k] for k in keys] }").unwrap();
+            let Expression::ListComprehension(comprehension) = returned(&function) else { panic!("Expected a comprehension") };
+            assert!(matches!(*comprehension.elements, Expression::DictAccess(_)));
+
+            // bloc
+            let function = resolve("fn get(d: Dict<str, int>, c: bool) -> int { return if c { let k = \"a\"; d[k] } else { 0 } }").unwrap();
+            let Expression::Conditional(conditional) = returned(&function) else { panic!("Expected a conditional") };
+            let Expression::Block(block) = &*conditional.then_block else { panic!("Expected a block") };
+            assert!(matches!(block.statements.last(), Some(ASTNode::Expression(Expression::DictAccess(_)))));
+        }
+
+        #[test]
+        fn test_user_type_uses_index_method() {
+            let function = resolve("fn cell(grid: Grid) -> int { return grid[3] }").unwrap();
+            assert!(matches!(returned(&function), Expression::MethodCall(call) if call.method == "index"));
+        }
+
+        #[test]
+        fn test_indexing_mistakes() {
+            assert_eq!(
+                resolve("fn get() -> int { let a = [1, 2]; return a[\"x\"] }").unwrap_err(),
+                "Index of Array(Int) must be Int, found String"
+            );
+            assert_eq!(
+                resolve("fn get(d: Dict<str, int>) -> int { return d[1] }").unwrap_err(),
+                "Dict key must be String, found Int"
+            );
+            assert!(resolve("fn get(n: int) -> int { return n[0] }").is_err());
+        }
+
+        #[test]
+        fn test_slice_types() {
+            assert!(check_function("fn tail(a: Vec<int>) -> Vec<int> { return a[1:] }").is_ok());
+            assert!(check_function("fn middle(s: str) -> str { return s[1..=3] }").is_ok());
+            assert!(check_function("fn reversed(s: str) -> str { return s[::-1] }").is_ok());
+            assert!(check_function("fn bad(a: Vec<int>) -> Vec<int> { return a[\"x\":] }").is_err());
+            assert!(check_function("fn bad(a: Vec<int>) -> Vec<int> { return a[::0] }").is_err());
+            assert!(check_function("fn bad(n: int) -> int { return n[1:] }").is_err());
+        }
+    }
+
+    mod struct_literal_tests {
+        use super::*;
+
+        const POINT: &str = "struct Point {x: int, y: int}";
+
+        #[test]
+        fn test_valid_literals() {
+            assert!(check_file(&format!("{} fn origin() -> Point {{ return Point {{ x: 0, y: 0 }} }}", POINT)).is_ok());
+            assert!(check_file(&format!("{} fn make(x: int, y: int) -> Point {{ return Point {{ x, y }} }}", POINT)).is_ok());
+            assert!(check_file(&format!("{} fn moved(p: Point) -> Point {{ return Point {{ x: 5, ..p }} }}", POINT)).is_ok());
+        }
+
+        #[test]
+        fn test_field_mistakes() {
+            let error = check_file(&format!("{} fn bad() -> Point {{ return Point {{ x: 0 }} }}", POINT)).unwrap_err();
+            assert_eq!(error, "Missing fields in 'Point' literal: y");
+            let error = check_file(&format!("{} fn bad() -> Point {{ return Point {{ x: 0, y: 0, z: 0 }} }}", POINT)).unwrap_err();
+            assert_eq!(error, "Struct 'Point' has no field 'z'");
+            assert!(check_file(&format!("{} fn bad() -> Point {{ return Point {{ x: 0, x: 1, y: 0 }} }}", POINT)).is_err());
+            assert!(check_file(&format!("{} fn bad() -> Point {{ return Point {{ x: \"a\", y: 0 }} }}", POINT)).is_err());
+            assert!(check_file(&format!("{} fn bad(n: int) -> Point {{ return Point {{ x: 0, ..n }} }}", POINT)).is_err());
+            assert!(check_file("fn bad() -> int { let s = Shape { x: 0 }; return 0 }").is_err());
+        }
+    }
+
+    mod tuple_tests {
+        use super::*;
+
+        #[test]
+        fn test_tuple_types() {
+            assert_eq!(infer("(1, \"a\")"), Ok(Type::Tuple(vec![Type::Int, Type::String])));
+            assert_eq!(infer("(1, 2.5).1"), Ok(Type::Float));
+            assert_eq!(infer("(1, 2).2"), Err("Tuple Tuple([Int, Int]) has no field 2".to_string()));
+        }
+
+        #[test]
+        fn test_let_destructuring() {
+            assert!(check_function("fn swap(p: (int, str)) -> str { let (n, mut s) = p; return s }").is_ok());
+            assert!(check_function("fn nested() -> bool { let (a, (b, c)) = (1, (\"x\", true)); return c }").is_ok());
+            assert!(check_function("fn wrong() -> int { let (a, b) = (1, \"x\"); return b }").is_err());
+            assert!(check_function("fn arity() -> int { let (a, b) = (1, 2, 3); return a }").is_err());
+            assert!(check_function("fn scalar() -> int { let (a, b) = 1; return a }").is_err());
+            assert!(check_function("fn annotated() -> int { let (a, b): (int, int) = (1, \"x\"); return a }").is_err());
+        }
+    }
+
+    mod argument_tests {
+        use super::*;
+
+        const CONNECT: &str = "fn connect(host: str, port: int = 80) -> bool { return true }";
+
+        #[test]
+        fn test_defaults_and_keyword_arguments() {
+            assert!(check_file(&format!("{} fn main() -> bool {{ return connect(\"x\") }}", CONNECT)).is_ok());
+            assert!(check_file(&format!("{} fn main() -> bool {{ return connect(\"x\", 8080) }}", CONNECT)).is_ok());
+            assert!(check_file(&format!("{} fn main() -> bool {{ return connect(port=8080, host=\"x\") }}", CONNECT)).is_ok());
+            assert!(check_file("fn connect(host: str, port: int = \"80\") -> bool { return true }").is_err());
+        }
+
+        #[test]
+        fn test_binding_errors() {
+            let call = |arguments: &str| check_file(&format!("{} fn main() -> bool {{ return connect({}) }}", CONNECT, arguments));
+            assert_eq!(call("port=1"), Err("connect() missing required argument 'host'".to_string()));
+            assert_eq!(call("\"x\", port=1, port=2"), Err("connect() got multiple values for argument 'port'".to_string()));
+            assert_eq!(call("\"x\", host=\"y\""), Err("connect() got multiple values for argument 'host'".to_string()));
+            assert_eq!(call("\"x\", timeout=1"), Err("connect() got an unexpected keyword argument 'timeout'".to_string()));
+            assert_eq!(call("\"x\", 1, 2"), Err("connect() takes 2 positional arguments but 3 were given".to_string()));
+            assert!(call("\"x\", port=\"80\"").is_err());
+        }
+
+        #[test]
+        fn test_duplicate_parameters() {
+            assert_eq!(check_function("fn f(a: int, a: int) -> int { return a }"), Err("Duplicate parameter 'a' in function 'f'".to_string()));
+            assert!(check_function("fn f(a: int, *a: int) -> int { return 0 }").is_err());
+            assert!(check_function("fn f(a: int, b: int) -> int { return a }").is_ok());
+        }
+
+        #[test]
+        fn test_variadics() {
+            let log = "fn log(level: int, *parts: str, **tags: bool) -> int { let n = parts; return level }";
+            assert!(check_file(&format!("{} fn main() -> int {{ return log(1, \"a\", \"b\", debug=true) }}", log)).is_ok());
+            assert!(check_file(&format!("{} fn main() -> int {{ return log(level=1) }}", log)).is_ok());
+            assert!(check_file(&format!("{} fn main() -> int {{ return log(1, 2) }}", log)).is_err());
+            assert!(check_file(&format!("{} fn main() -> int {{ return log(1, debug=3) }}", log)).is_err());
+            assert!(check_file("fn first(*items: int) -> int { return items[0] }").is_ok());
+        }
+    }
+
+    mod pattern_tests {
+        use super::*;
+
+        #[test]
+        fn test_if_let_bindings() {
+            assert!(check_function("fn f(opt: Option<int>) -> int { if let Some(x) = opt { return x } return 0 }").is_ok());
+            assert!(check_function("fn f(opt: Option<int>) -> str { if let Some(x) = opt { return x } return \"\" }").is_err());
+            assert!(check_function("fn f(res: Result<int, str>) -> str { if let Err(e) = res { return e } return \"\" }").is_ok());
+            assert_eq!(
+                check_function("fn f(n: int) -> int { if let Some(x) = n { return x } return 0 }"),
+                Err("Pattern 'Some(..)' cannot match Int".to_string())
+            );
+        }
+
+        #[test]
+        fn test_while_let_rest() {
+            assert!(check_function("fn f(items: Array<int>) -> Array<int> { while let [head, ..rest] = items { items = rest; } return items }").is_ok());
+            assert!(check_function("fn f(items: Array<int>) -> int { if let [head, ..rest] = items { return head } return 0 }").is_ok());
+            assert!(check_function("fn f(items: Array<int>) -> int { if let [head, ..rest] = items { return rest } return 0 }").is_err());
+        }
+
+        #[test]
+        fn test_or_and_binding_patterns() {
+            assert!(check_function("fn f(n: int) -> int { if let d @ 1..=9 = n { return d } return 0 }").is_ok());
+            assert!(check_function("fn f(p: (int, int)) -> int { if let (x, 0) | (0, x) = p { return x } return 0 }").is_ok());
+            let error = check_function("fn f(p: (int, int)) -> int { if let (x, 0) | (0, y) = p { return 0 } return 0 }").unwrap_err();
+            assert!(error.starts_with("Every alternative of an or-pattern must bind the same names"));
+        }
+
+        #[test]
+        fn test_struct_patterns() {
+            const POINT: &str = "struct Point {x: int, y: int}";
+            assert!(check_file(&format!("{} fn f(p: Point) -> int {{ if let Point {{ x, y: 0 }} = p {{ return x }} return 0 }}", POINT)).is_ok());
+            assert!(check_file(&format!("{} fn f(p: Point) -> int {{ if let Point {{ y: n, .. }} = p {{ return n }} return 0 }}", POINT)).is_ok());
+            let error = check_file(&format!("{} fn f(p: Point) -> int {{ if let Point {{ x }} = p {{ return x }} return 0 }}", POINT)).unwrap_err();
+            assert_eq!(error, "Missing fields in 'Point' pattern: y");
+            let error = check_file(&format!("{} fn f(p: Point) -> int {{ if let Point {{ z, .. }} = p {{ return 0 }} return 0 }}", POINT)).unwrap_err();
+            assert_eq!(error, "Struct 'Point' has no field 'z'");
+            assert!(check_file(&format!("{} fn f(n: int) -> int {{ if let Point {{ .. }} = n {{ return 0 }} return 0 }}", POINT)).is_err());
+        }
+    }
+
+    mod closure_tests {
+        use pyrust::parser::ast::{CaptureMode, Statement};
+        use super::*;
+
+        // captures de la fermeture renvoyee par la derniere instruction
+        fn returned_captures(function: &FunctionDeclaration) -> Vec<(String, CaptureMode)> {
+            let Some(ASTNode::Statement(Statement::ReturnStatement(ret))) = function.body.last() else {
+                panic!("Expected a return statement");
+            };
+            let Some(Expression::LambdaExpression(lambda)) = &ret.value else { panic!("Expected a closure") };
+            lambda.captures.iter().map(|capture| (capture.name.clone(), capture.mode)).collect()
+        }
+
+        #[test]
+        fn test_capture_modes() {
+            let function = resolve("fn f(n: int) { let offset = 2; return |x| x + n + offset }").unwrap();
+            assert_eq!(returned_captures(&function), [("n".to_string(), CaptureMode::Reference), ("offset".to_string(), CaptureMode::Reference)]);
+
+            let function = resolve("fn f() { let mut count = 0; return || count += 1 }").unwrap();
+            assert_eq!(returned_captures(&function), [("count".to_string(), CaptureMode::MutableReference)]);
+
+            let function = resolve("fn f(n: int) { let mut total = 0; return move |x| total += x * n }").unwrap();
+            assert_eq!(returned_captures(&function), [("total".to_string(), CaptureMode::Move), ("n".to_string(), CaptureMode::Move)]);
+        }
+
+        #[test]
+        fn test_locals_and_nested_closures() {
+            // parametres et variables propres a la fermeture ne sont pas captures
+            let function = resolve("fn f() { return |x| { let y = x * 2; return y } }").unwrap();
+            assert!(returned_captures(&function).is_empty());
+
+            // la fermeture externe capture ce que capture la fermeture interne
+            let function = resolve("fn f(n: int) { return |x| |y| x + y + n }").unwrap();
+            assert_eq!(returned_captures(&function), [("n".to_string(), CaptureMode::Reference)]);
+        }
+
+        #[test]
+        fn test_assignment_declares_unbound_names() {
+            // `count = 0` declare `count` (forme usuelle du mode indentation)
+            let function = resolve("fn f() { count = 0; return || count += 1 }").unwrap();
+            assert_eq!(returned_captures(&function), [("count".to_string(), CaptureMode::MutableReference)]);
+
+            // une affectation dans la fermeture declare une variable locale
+            let function = resolve("fn f() { return |x| { y = x; return y } }").unwrap();
+            assert!(returned_captures(&function).is_empty());
+        }
+
+        #[test]
+        fn test_or_patterns_declare_every_alternative() {
+            // `x` est lie par chaque alternative : ce n'est pas le `x` de la fonction
+            let function = resolve("fn f(x: int) { return |p| { match p { (x, 0) | (0, x) => x, _ => 0 } } }").unwrap();
+            assert!(returned_captures(&function).is_empty());
+            let function = resolve("fn f(x: int) { return |p| { match p { (y, 0) | (0, y) => x + y, _ => 0 } } }").unwrap();
+            assert_eq!(returned_captures(&function), [("x".to_string(), CaptureMode::Reference)]);
+        }
+
+        #[test]
+        fn test_assigning_immutable_capture() {
+            let error = resolve("fn f() { let count = 0; return || count += 1 }").unwrap_err();
+            assert_eq!(error, "Cannot assign to captured variable 'count': it is not declared 'mut'");
+            assert!(resolve("fn f() { let count = 0; count += 1; return count }").is_ok());
+        }
+
+        #[test]
+        fn test_closure_types() {
+            assert!(check_function("fn f(n: int) -> int { let add = |x: int| x + n; return add(1) }").is_ok());
+            assert!(check_function("fn make_adder(n: int) -> fn(int) -> int { return move |x: int| x + n }").is_ok());
+            assert!(check_function("fn apply(g: fn(int) -> int) -> int { return g(2) }").is_ok());
+            assert!(check_function("fn f() -> int { let add = |x: int, y: int| x + y; return add(1) }").is_err());
+            assert!(check_function("fn f() -> str { let double = |x: int| x * 2; return double(1) }").is_err());
+            assert!(check_function("fn f() -> int { let g: fn(int) -> int = |x: int| x > 0; return 0 }").is_err());
+        }
+    }
+}